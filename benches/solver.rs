@@ -0,0 +1,35 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use wordle_solve::{read_words, score_guess_count_eliminations, Constraint, Scoring,
+    WordleSolver, Word};
+
+/// A fixed slice of the full word list, so results are comparable between
+/// runs.
+fn words() -> Vec<Word>
+{
+    let path = format!("{}/benches/data/words", env!("CARGO_MANIFEST_DIR"));
+    read_words(&path, None).unwrap().words
+}
+
+fn best_guess(c: &mut Criterion)
+{
+    let words = words();
+    let constraint = Constraint::new(words[0].len());
+    // Build a new solver every time, or the first guess would be cached.
+    c.bench_function("best_guess", |b| b.iter_batched(
+        || WordleSolver::new(words.clone(), None, None, Scoring::Eliminations),
+        |solver| solver.best_guess(&constraint, false).unwrap().as_str().to_string(),
+        BatchSize::SmallInput));
+}
+
+fn count_eliminations(c: &mut Criterion)
+{
+    let words = words();
+    let constraint = Constraint::new(words[0].len());
+    let candidates: Vec<&Word> = words.iter().collect();
+    let guess = &words[0];
+    c.bench_function("score_guess_count_eliminations", |b| b.iter(
+        || score_guess_count_eliminations(guess, &candidates, &constraint)));
+}
+
+criterion_group!(benches, best_guess, count_eliminations);
+criterion_main!(benches);
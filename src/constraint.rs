@@ -0,0 +1,774 @@
+use crate::word::Word;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::cmp;
+use std::collections::{HashSet, HashMap};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CharacterConstraint {
+    pub(crate) is: Option<char>,
+    pub(crate) is_not: HashSet<char>
+}
+
+/// How a result row is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RowFormat {
+    /// A prefix on each letter for its color, e.g. "-c ~r a -n -e".
+    Prefix,
+    /// The guess and a G, Y or X for each letter, e.g. "crane XYGXX".
+    Gyx,
+    /// The guess and the colored squares, e.g. "crane ⬛🟨🟩⬛⬛".
+    Emoji
+}
+
+/// The characters that mark a letter's color in a row written the way
+/// `Constraint::from_string` reads it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowOps {
+    pub gray: char,
+    pub yellow: char,
+    /// Green letters can always be written bare, without a marker.
+    pub green: Option<char>
+}
+
+impl Default for RowOps {
+    fn default() -> Self {
+        RowOps { gray: '-', yellow: '~', green: None }
+    }
+}
+
+impl FromStr for RowOps {
+    type Err = String;
+
+    /// Parse the gray and yellow markers, and optionally the green one,
+    /// separated by spaces, e.g. `X Y .`.
+    fn from_str(string: &str) -> Result<Self, String> {
+        let usage = format!("'{}' should be the gray and yellow markers, and optionally the green one, \
+            separated by spaces, e.g. \"X Y .\".", string);
+        let mut markers = Vec::new();
+        for marker in string.split_whitespace() {
+            let mut chars = marker.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '!' && c != '=' && c != '+' && !markers.contains(&c) => markers.push(c),
+                _ => return Err(usage)
+            }
+        }
+        match markers[..] {
+            [gray, yellow] => Ok(RowOps { gray, yellow, green: None }),
+            [gray, yellow, green] => Ok(RowOps { gray, yellow, green: Some(green) }),
+            _ => Err(usage)
+        }
+    }
+}
+
+/// Everything known about the answer so far. Build one from result rows and
+/// merge them with `update`; it can be saved with serde.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Constraint {
+    pub(crate) character: Vec<CharacterConstraint>,
+    // For each char, track how many there are at least in the word.
+    pub(crate) min_occurrence: HashMap<char, usize>,
+    pub(crate) max_occurrence: HashMap<char, usize>,
+    // Letters that aren't anywhere in the word.
+    #[serde(default)]
+    pub(crate) forbidden: HashSet<char>
+}
+
+impl Constraint {
+    pub fn new(size: usize) -> Self {
+        let character = vec![CharacterConstraint {is: None, is_not: HashSet::new() }; size];
+        Self {
+            character,
+            min_occurrence: HashMap::new(),
+            max_occurrence: HashMap::new(),
+            forbidden: HashSet::new()
+        }
+    }
+
+    /// Parse a single wordle result row, e.g. `-r -a ~i -s -e`. Each letter
+    /// is separated by a space, and gray and yellow letters are prefixed with
+    /// `-` and `~` respectively. A letter prefixed with `!` isn't anywhere in
+    /// the word, and doesn't count towards the row's length. Neither does
+    /// `=Nx`, which says there are exactly N copies of x in the word, or
+    /// `+x`, which says x is in the word somewhere.
+    pub fn from_string(string: &str, size: usize) -> Result<Self, String> {
+        Constraint::from_string_with_ops(string, size, &RowOps::default())
+    }
+
+    /// Like `from_string`, but with `ops` marking the colors instead of `-`
+    /// and `~`, e.g. `Xr Xa Yi Xs .e`.
+    pub fn from_string_with_ops(string: &str, size: usize, ops: &RowOps) -> Result<Self, String> {
+        let mut letters = Vec::new();
+        let mut forbidden = HashSet::new();
+        let mut exact: HashMap<char, usize> = HashMap::new();
+        let mut present: HashMap<char, usize> = HashMap::new();
+        for token in string.split(' ') {
+            if let Some(rest) = token.strip_prefix('+') {
+                let mut letter = rest.chars();
+                match (letter.next(), letter.next()) {
+                    (Some(x), None) => *present.entry(x).or_insert(0) += 1,
+                    _ => return Err(format!("'{}' in row '{}' should be + followed by a letter, e.g. +e.",
+                        token, string))
+                }
+                continue;
+            }
+            if let Some(rest) = token.strip_prefix('=') {
+                let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+                let mut letter = rest[digits..].chars();
+                match (rest[..digits].parse::<usize>(), letter.next(), letter.next()) {
+                    (Ok(n), Some(x), None) => { exact.insert(x, n); },
+                    _ => return Err(format!("'{}' in row '{}' should be = followed by a count and a letter, e.g. =2e.",
+                        token, string))
+                }
+                continue;
+            }
+            let mut chars = token.chars();
+            letters.push(match (chars.next(), chars.next(), chars.next()) {
+                (Some('!'), Some(x), None) => {
+                    forbidden.insert(x);
+                    continue;
+                },
+                (Some(p), Some(x), None) if p == ops.gray => (Feedback::Gray, x),
+                (Some(p), Some(x), None) if p == ops.yellow => (Feedback::Yellow, x),
+                (Some(p), Some(x), None) if Some(p) == ops.green => (Feedback::Green, x),
+                (Some(p), None, None) if p == '!' || p == ops.gray || p == ops.yellow || Some(p) == ops.green =>
+                    return Err(format!("'{}' in row '{}' isn't followed by a letter.", p, string)),
+                (Some(x), None, None) => (Feedback::Green, x),
+                (None, _, _) =>
+                    return Err(format!("Row '{}' has a missing letter (two spaces in a row?).", string)),
+                _ => return Err(format!("'{}' in row '{}' should be a single letter, optionally prefixed with {}, {} or !.",
+                    token, string, ops.gray, ops.yellow))
+            });
+        }
+        // A row of only forbidden, present letters and counts is fine too.
+        let mut constraint = if letters.is_empty() &&
+                !(forbidden.is_empty() && exact.is_empty() && present.is_empty()) {
+            Constraint::new(size)
+        } else if letters.len() != size {
+            return Err(format!("Row '{}' has {} letters but word length is {}.",
+                string, letters.len(), size));
+        } else {
+            Constraint::from_feedback(&letters)
+        };
+        constraint.forbidden = forbidden;
+        for (c, n) in present {
+            let min = constraint.min_occurrence.entry(c).or_insert(0);
+            *min = cmp::max(*min, n);
+        }
+        for (c, n) in exact {
+            let min = constraint.min_occurrence.entry(c).or_insert(0);
+            *min = cmp::max(*min, n);
+            let max = constraint.max_occurrence.entry(c).or_insert(n);
+            *max = cmp::min(*max, n);
+        }
+        Ok(constraint)
+    }
+
+    /// Build the constraint for one result row, given each letter and its
+    /// color. Shared by `from_string` and `wordle_guess`.
+    fn from_feedback(letters: &[(Feedback, char)]) -> Self {
+        let mut constraint = Constraint::new(letters.len());
+        let mut count: HashMap<char, usize> = HashMap::new();
+        let mut found_max = HashSet::new();
+        for (i, &(f, x)) in letters.iter().enumerate() {
+            match f {
+                Feedback::Green => {
+                    constraint.character[i].is = Some(x);
+                    constraint.increment_min_occurrence(&x);
+                    count.entry(x).and_modify(|n| *n += 1).or_insert(1);
+                },
+                Feedback::Yellow => {
+                    constraint.character[i].is_not.insert(x);
+                    constraint.increment_min_occurrence(&x);
+                    count.entry(x).and_modify(|n| *n += 1).or_insert(1);
+                },
+                Feedback::Gray => {
+                    constraint.character[i].is_not.insert(x);
+                    found_max.insert(x);
+                }
+            }
+        }
+        // A gray letter means the answer has no more of that letter than the
+        // row has green and yellow copies of it.
+        for c in found_max {
+            constraint.max_occurrence.insert(c, *count.get(&c).unwrap_or(&0));
+        }
+        constraint
+    }
+
+    /// Build the constraint for a shared result row, given the word that was
+    /// guessed and its colored squares, e.g. `crane` and `⬛🟨🟩⬛⬛`.
+    pub fn from_guess_and_pattern(guess: &str, pattern: &str) -> Result<Self, String> {
+        Constraint::from_guess_and_marks(guess, pattern, |square| match square {
+            '🟩' => Some(""),
+            '🟨' => Some("~"),
+            '⬛' | '⬜' => Some("-"),
+            _ => None
+        })
+    }
+
+    /// Like `from_guess_and_pattern`, but with a G, Y or X for each letter
+    /// instead of a square, e.g. `crane` and `XYGXX`.
+    pub fn from_guess_and_gyx(guess: &str, colors: &str) -> Result<Self, String> {
+        Constraint::from_guess_and_marks(guess, colors, |color| match color.to_ascii_uppercase() {
+            'G' => Some(""),
+            'Y' => Some("~"),
+            'X' => Some("-"),
+            _ => None
+        })
+    }
+
+    /// Build a row from the guess and a mark for each of its letters.
+    /// `prefix` turns a mark into the prefix `from_string` uses for its color.
+    fn from_guess_and_marks(guess: &str, marks: &str, prefix: fn(char) -> Option<&'static str>) ->
+            Result<Self, String> {
+        // Some platforms follow each square with an emoji variation selector.
+        let squares: Vec<char> = marks.chars().filter(|c| *c != '\u{fe0f}').collect();
+        let letters: Vec<char> = guess.chars().collect();
+        if squares.len() != letters.len() {
+            return Err(format!("Pattern '{}' has {} squares but '{}' has {} letters.",
+                marks, squares.len(), guess, letters.len()));
+        }
+
+        let mut row = Vec::new();
+        for (letter, square) in letters.iter().zip(squares.iter()) {
+            match prefix(*square) {
+                Some(prefix) => row.push(format!("{}{}", prefix, letter)),
+                None => return Err(format!("Unexpected '{}' in pattern '{}'.", square, marks))
+            }
+        }
+        Constraint::from_string(&row.join(" "), letters.len())
+    }
+
+    /// Parse a result row written in `format`. Apart from `Prefix` rows, a
+    /// row is the guess followed by its result, separated by spaces.
+    pub fn from_row(row: &str, format: RowFormat, length: usize) -> Result<Self, String> {
+        if format == RowFormat::Prefix {
+            return Constraint::from_string(row, length);
+        }
+        let (guess, result) = row.trim().split_once(char::is_whitespace)
+                .ok_or(format!("Row '{}' should be the guess followed by its result.", row))?;
+        let constraint = match format {
+            RowFormat::Gyx => Constraint::from_guess_and_gyx(guess, result.trim())?,
+            _ => Constraint::from_guess_and_pattern(guess, result.trim())?
+        };
+        if constraint.len() != length {
+            return Err(format!("'{}' has {} letters, but the words have {}.", guess, constraint.len(), length));
+        }
+        Ok(constraint)
+    }
+
+    /// Build the constraint for letters that are given before the first
+    /// guess, e.g. `_ r _ _ _`: a letter for each green, and `_` where the
+    /// letter isn't known. Spaces are optional.
+    pub fn from_given(given: &str, length: usize) -> Result<Self, String> {
+        let letters: Vec<char> = given.chars().filter(|c| !c.is_whitespace()).collect();
+        if letters.len() != length {
+            return Err(format!("'{}' has {} letters and blanks, but the words have {} letters.",
+                given, letters.len(), length));
+        }
+        let mut constraint = Constraint::new(length);
+        for (pos, c) in letters.into_iter().enumerate() {
+            match c {
+                '_' => {},
+                c if c.is_alphabetic() => { constraint.set_green(pos, c); },
+                c => return Err(format!("Unexpected '{}' in '{}'. Use a letter or _ for each position.", c, given))
+            }
+        }
+        Ok(constraint)
+    }
+
+    /// Build the constraint for a whole shared game, pasted with the guessed
+    /// words: every guess on its own line, followed by a line with its
+    /// colored squares. Blank lines are ignored.
+    pub fn from_share(share: &str) -> Result<Self, String> {
+        let lines: Vec<&str> = share.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        if lines.is_empty() || !lines.len().is_multiple_of(2) {
+            return Err("A share should have a line with each guess, followed by a line with its squares.".to_string());
+        }
+
+        let mut constraint: Option<Constraint> = None;
+        for pair in lines.chunks(2) {
+            let row = Constraint::from_guess_and_pattern(pair[0], pair[1])?;
+            match constraint.as_mut() {
+                Some(constraint) if constraint.len() != row.len() => {
+                    return Err(format!("'{}' has {} letters but the first guess has {}.",
+                        pair[0], row.len(), constraint.len()));
+                },
+                Some(constraint) => constraint.update(&row),
+                None => constraint = Some(row)
+            }
+        }
+        Ok(constraint.unwrap())
+    }
+
+    /// The length of the words this constraint is for.
+    pub fn len(&self) -> usize {
+        self.character.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.character.is_empty()
+    }
+
+    /// Whether every letter is known, i.e. the word has been guessed.
+    pub fn is_solved(&self) -> bool {
+        self.character.iter().all(|c| c.is.is_some())
+    }
+
+    /// Whether any word could satisfy the constraint, as far as
+    /// `contradiction` can tell.
+    pub fn is_satisfiable(&self) -> bool {
+        self.contradiction().is_none()
+    }
+
+    /// Explain why no word can satisfy the constraint, if that's obvious from
+    /// the constraint alone.
+    pub fn contradiction(&self) -> Option<String> {
+        for (i, cc) in self.character.iter().enumerate() {
+            if let Some(x) = cc.is {
+                if cc.is_not.contains(&x) {
+                    return Some(format!("letter {} is {} but also can't be {}.", i + 1, x, x));
+                }
+            }
+        }
+
+        let mut letters: Vec<&char> = self.min_occurrence.keys()
+                .chain(self.max_occurrence.keys())
+                .collect();
+        letters.sort();
+        letters.dedup();
+        for c in letters {
+            let min = *self.min_occurrence.get(c).unwrap_or(&0);
+            let green = self.character.iter().filter(|cc| cc.is == Some(*c)).count();
+            if let Some(max) = self.max_occurrence.get(c) {
+                if min > 0 && *max == 0 {
+                    return Some(format!("{} is gray, so it isn't in the word, but it's also green or yellow.", c));
+                }
+                if min > *max {
+                    return Some(format!("there are at least {} copies of {} but at most {}.", min, c, max));
+                }
+                if green > *max {
+                    return Some(format!("{} is green in {} places but there are at most {}.", c, green, max));
+                }
+            }
+            if (min > 0 || green > 0) && self.forbidden.contains(c) {
+                return Some(format!("{} is green or yellow but also isn't anywhere in the word.", c));
+            }
+        }
+
+        let known: usize = self.min_occurrence.values().sum();
+        if known > self.character.len() {
+            return Some(format!("there are at least {} known letters but words only have {}.",
+                known, self.character.len()));
+        }
+        None
+    }
+
+    pub fn increment_min_occurrence(&mut self, c: &char) {
+        self.min_occurrence.entry(*c).and_modify(|n| *n += 1).or_insert(1);
+    }
+
+    // The methods below build up a single result row one letter at a time,
+    // keeping count the way `from_string` does. Build each row separately and
+    // merge them with `update`. They panic if `pos` is out of range.
+
+    /// Letter `pos` (counting from 0) is green.
+    pub fn set_green(&mut self, pos: usize, c: char) -> &mut Self {
+        if self.character[pos].is != Some(c) {
+            self.character[pos].is = Some(c);
+            self.add_known(c);
+        }
+        self
+    }
+
+    /// Letter `pos` (counting from 0) is yellow.
+    pub fn add_yellow(&mut self, pos: usize, c: char) -> &mut Self {
+        self.character[pos].is_not.insert(c);
+        self.add_known(c);
+        self
+    }
+
+    /// `c` is gray, so the answer has no more copies of it than the row has
+    /// green and yellow ones, whichever order they're added in.
+    pub fn add_gray(&mut self, c: char) -> &mut Self {
+        let count = *self.min_occurrence.get(&c).unwrap_or(&0);
+        self.max_occurrence.insert(c, count);
+        self
+    }
+
+    /// Count one more green or yellow `c`, raising its maximum if a gray copy
+    /// was added first.
+    fn add_known(&mut self, c: char) {
+        self.increment_min_occurrence(&c);
+        let min = self.min_occurrence[&c];
+        if let Some(max) = self.max_occurrence.get_mut(&c) {
+            *max = cmp::max(*max, min);
+        }
+    }
+
+    pub fn update(&mut self, constraint: &Constraint) {
+        for (c, count) in constraint.min_occurrence.iter() {
+            self.min_occurrence.entry(*c)
+                    .and_modify(|v| *v = cmp::max(*v, *count))
+                    .or_insert(*count);
+        }
+        for (c, count) in constraint.max_occurrence.iter() {
+            self.max_occurrence.entry(*c)
+                    .and_modify(|v| *v = cmp::min(*v, *count))
+                    .or_insert(*count);
+        }
+        self.forbidden.extend(constraint.forbidden.iter());
+        for (my_c, other_c) in self.character.iter_mut().zip(constraint.character.iter()) {
+            if other_c.is.is_none() {
+                for c in other_c.is_not.iter() {
+                    my_c.is_not.insert(*c);
+                }
+            } else {
+                my_c.is = other_c.is;
+            }
+        }
+    }
+
+    /// Like `update`, but check that the result allows exactly the `words`
+    /// that both constraints allow, and that the rows don't contradict each
+    /// other. That goes through every word, so it's only worth it to catch
+    /// rows that disagree, or a mistake in `update`. On an error, `self` is
+    /// left as it was.
+    pub fn update_checked(&mut self, constraint: &Constraint, words: &[Word]) -> Result<(), String> {
+        let mut merged = self.clone();
+        merged.update(constraint);
+        if let Some(reason) = merged.contradiction() {
+            return Err(format!("The rows contradict each other: {}", reason));
+        }
+        for word in words {
+            match (self.allows(word), constraint.allows(word), merged.allows(word)) {
+                (true, true, false) => return Err(format!(
+                    "Merging the rows rules out '{}', though every row allows it.", word.as_str())),
+                (false, _, true) => return Err(format!(
+                    "Merging the rows allows '{}', though the earlier rows rule it out.", word.as_str())),
+                (_, false, true) => return Err(format!(
+                    "Merging the rows allows '{}', though the new row rules it out.", word.as_str())),
+                _ => {}
+            }
+        }
+        *self = merged;
+        Ok(())
+    }
+
+    pub fn allows(&self, word: &Word) -> bool
+    {
+        self.min_occurrence.iter()
+                .all(|(key, value)| word.char_count(key) >= *value) &&
+        self.max_occurrence.iter()
+                .all(|(key, value)| word.char_count(key) <= *value) &&
+        self.forbidden.iter().all(|c| word.char_count(c) == 0) &&
+        // Check that green letters are where they should be.
+        self.character.iter().zip(word.chars())
+                .all(|(cc, y)|
+                        match cc.is {
+                            None => true,
+                            Some(x) => x == y
+                        }) &&
+        self.character.iter().zip(word.chars())
+                .all(|(cc, y)| !cc.is_not.contains(&y))
+    }
+
+    /// How many different letters in `word` nothing is known about yet:
+    /// they aren't green anywhere, ruled out anywhere, or counted.
+    pub fn untested_letters(&self, word: &Word) -> usize {
+        let known: HashSet<char> = self.character.iter()
+                .flat_map(|cc| cc.is.iter().chain(cc.is_not.iter()))
+                .chain(self.min_occurrence.keys())
+                .chain(self.max_occurrence.keys())
+                .chain(self.forbidden.iter())
+                .copied()
+                .collect();
+        let letters: HashSet<char> = word.chars().filter(|c| !known.contains(c)).collect();
+        letters.len()
+    }
+
+    /// Pack the constraint into a `CompactConstraint`, if it's for five
+    /// letter words and only mentions lowercase ASCII letters.
+    pub fn compact(&self) -> Option<CompactConstraint> {
+        if self.len() != COMPACT_LENGTH {
+            return None;
+        }
+        let mut compact = CompactConstraint {
+            green: [None; COMPACT_LENGTH],
+            not_here: [0; COMPACT_LENGTH],
+            min: [0; 26],
+            max: [u8::MAX; 26]
+        };
+        for (i, cc) in self.character.iter().enumerate() {
+            if let Some(c) = cc.is {
+                compact.green[i] = Some(letter_index(c)?);
+            }
+            for c in &cc.is_not {
+                compact.not_here[i] |= 1 << letter_index(*c)?;
+            }
+        }
+        for (c, n) in &self.min_occurrence {
+            compact.min[letter_index(*c)? as usize] = cmp::min(*n, u8::MAX as usize) as u8;
+        }
+        for (c, n) in &self.max_occurrence {
+            let max = &mut compact.max[letter_index(*c)? as usize];
+            *max = cmp::min(*max as usize, *n) as u8;
+        }
+        for c in &self.forbidden {
+            compact.max[letter_index(*c)? as usize] = 0;
+        }
+        Some(compact)
+    }
+}
+
+/// The word length `CompactConstraint` handles.
+const COMPACT_LENGTH: usize = 5;
+
+/// The position of `c` in the alphabet, if it's a lowercase ASCII letter.
+fn letter_index(c: char) -> Option<u8> {
+    c.is_ascii_lowercase().then(|| c as u8 - b'a')
+}
+
+/// The same as a `Constraint` for five letter lowercase ASCII words, packed
+/// into arrays so that checking a word takes a few array lookups and bit
+/// operations instead of hash lookups. Build one with
+/// `Constraint::compact`.
+#[derive(Clone, Debug)]
+pub struct CompactConstraint {
+    /// The letter known to be in each position, from 0 for a to 25 for z.
+    green: [Option<u8>; COMPACT_LENGTH],
+    /// Bit n is set when letter n isn't in that position.
+    not_here: [u32; COMPACT_LENGTH],
+    /// How many of each letter there are at least and at most.
+    min: [u8; 26],
+    max: [u8; 26]
+}
+
+impl CompactConstraint {
+    /// Whether `word` satisfies the constraint, the same as
+    /// `Constraint::allows`. Words that aren't five lowercase ASCII letters
+    /// never do.
+    pub fn allows_fast(&self, word: &Word) -> bool {
+        let (Some(bytes), Some(frequency)) = (word.ascii(), word.ascii_frequency()) else {
+            return false;
+        };
+        if bytes.len() != COMPACT_LENGTH {
+            return false;
+        }
+        for (i, b) in bytes.iter().enumerate() {
+            let letter = b - b'a';
+            if self.green[i].is_some_and(|green| green != letter) || self.not_here[i] & (1 << letter) != 0 {
+                return false;
+            }
+        }
+        frequency.iter().zip(self.min.iter().zip(self.max.iter()))
+                .all(|(n, (min, max))| min <= n && n <= max)
+    }
+}
+
+/// Hash the letters and counts in order, so that equal constraints hash the
+/// same however their sets and maps happen to be laid out.
+impl Hash for Constraint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for cc in &self.character {
+            cc.is.hash(state);
+            sorted(cc.is_not.iter()).hash(state);
+        }
+        sorted(self.min_occurrence.iter()).hash(state);
+        sorted(self.max_occurrence.iter()).hash(state);
+        sorted(self.forbidden.iter()).hash(state);
+    }
+}
+
+fn sorted<T: Ord>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut items: Vec<T> = items.collect();
+    items.sort();
+    items
+}
+
+/// Sorted, space separated list of letters.
+fn letter_list<'a>(letters: impl Iterator<Item = &'a char>) -> String {
+    let mut letters: Vec<String> = letters.map(|c| c.to_string()).collect();
+    letters.sort();
+    letters.join(" ")
+}
+
+/// Sorted list of letters with their counts, e.g. `1 e, 2 s`.
+fn occurrence_list(occurrences: &HashMap<char, usize>) -> String {
+    let mut letters: Vec<_> = occurrences.iter().collect();
+    letters.sort();
+    letters.iter()
+            .map(|(c, n)| format!("{} {}", n, c))
+            .collect::<Vec<_>>()
+            .join(", ")
+}
+
+/// Show what's known about the answer, one kind of fact per line, e.g.
+/// `Green:    _ r _ _ e`. Lines with nothing to say are left out.
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let green: Vec<String> = self.character.iter()
+                .map(|cc| cc.is.map_or("_".to_string(), |c| c.to_string()))
+                .collect();
+        write!(f, "Green:    {}", green.join(" "))?;
+        for (i, cc) in self.character.iter().enumerate() {
+            if cc.is.is_none() && !cc.is_not.is_empty() {
+                write!(f, "\nNot at {}: {}", i + 1, letter_list(cc.is_not.iter()))?;
+            }
+        }
+        if !self.min_occurrence.is_empty() {
+            write!(f, "\nAt least: {}", occurrence_list(&self.min_occurrence))?;
+        }
+        if !self.max_occurrence.is_empty() {
+            write!(f, "\nAt most:  {}", occurrence_list(&self.max_occurrence))?;
+        }
+        if !self.forbidden.is_empty() {
+            write!(f, "\nNowhere:  {}", letter_list(self.forbidden.iter()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Return the constraint wordle reveals when `guess` is played against
+/// `answer`.
+pub fn wordle_guess(guess: &Word, answer: &Word) -> Constraint
+{
+    let letters: Vec<(Feedback, char)> = feedback(guess, answer).into_iter()
+            .zip(guess.chars())
+            .collect();
+    Constraint::from_feedback(&letters)
+}
+
+/// The color of a single letter in a wordle result row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Feedback {
+    Gray,
+    Yellow,
+    Green
+}
+
+/// Return the colors wordle shows for `guess` when the answer is `answer`.
+pub(crate) fn feedback(guess: &Word, answer: &Word) -> Vec<Feedback>
+{
+    if let (Some(guess), Some(answer)) = (guess.ascii(), answer.ascii()) {
+        return ascii_feedback(guess, answer);
+    }
+
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    let mut result = vec![Feedback::Gray; guess.len()];
+
+    // Letters in the answer that haven't been matched by a green yet.
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+    for (i, (g, a)) in guess.iter().zip(answer.iter()).enumerate() {
+        if g == a {
+            result[i] = Feedback::Green;
+        } else {
+            unmatched.entry(*a).and_modify(|n| *n += 1).or_insert(1);
+        }
+    }
+
+    for (i, g) in guess.iter().enumerate() {
+        if result[i] == Feedback::Green {
+            continue;
+        }
+        if let Some(n) = unmatched.get_mut(g) {
+            if *n > 0 {
+                *n -= 1;
+                result[i] = Feedback::Yellow;
+            }
+        }
+    }
+
+    result
+}
+
+/// The same as `feedback`, for words that only have lowercase ASCII letters.
+fn ascii_feedback(guess: &[u8], answer: &[u8]) -> Vec<Feedback>
+{
+    let mut result = vec![Feedback::Gray; guess.len()];
+
+    let mut unmatched = [0u8; 26];
+    for (i, (g, a)) in guess.iter().zip(answer.iter()).enumerate() {
+        if g == a {
+            result[i] = Feedback::Green;
+        } else {
+            unmatched[(a - b'a') as usize] += 1;
+        }
+    }
+
+    for (i, g) in guess.iter().enumerate() {
+        let n = &mut unmatched[(g - b'a') as usize];
+        if result[i] != Feedback::Green && *n > 0 {
+            *n -= 1;
+            result[i] = Feedback::Yellow;
+        }
+    }
+
+    result
+}
+
+/// Return the feedback for `guess` against `answer` as a string, using G for
+/// green, Y for yellow and X for gray.
+pub fn feedback_pattern(guess: &Word, answer: &Word) -> String
+{
+    feedback(guess, answer).iter()
+            .map(|f| match f {
+                Feedback::Gray => 'X',
+                Feedback::Yellow => 'Y',
+                Feedback::Green => 'G'
+            })
+            .collect()
+}
+
+/// Encode the feedback for `guess` against `answer` as a base 3 number, with
+/// one digit per letter. Only words of up to `MAX_CODE_LENGTH` letters fit.
+pub(crate) fn feedback_code(guess: &Word, answer: &Word) -> u16
+{
+    feedback(guess, answer).iter()
+            .rev()
+            .fold(0, |code, f| code * 3 + match f {
+                Feedback::Gray => 0,
+                Feedback::Yellow => 1,
+                Feedback::Green => 2
+            })
+}
+
+/// The longest word whose feedback fits in a `feedback_code`.
+pub(crate) const MAX_CODE_LENGTH: usize = 10;
+
+/// Return the words in `words` that satisfy `constraint`.
+pub fn filter_words<'a>(constraint: &Constraint, words: &'a [Word]) -> Vec<&'a Word>
+{
+    narrow_words(constraint, words.iter())
+}
+
+/// Like `filter_words`, but for any words, e.g. ones that were already
+/// narrowed down by earlier constraints.
+pub(crate) fn narrow_words<'a>(constraint: &Constraint, words: impl Iterator<Item = &'a Word>) -> Vec<&'a Word>
+{
+    let compact = constraint.compact();
+    words.filter(|word| allowed(constraint, compact.as_ref(), word)).collect()
+}
+
+/// Like `filter_words`, but return the indices of the words in `words`.
+pub(crate) fn filter_indices(constraint: &Constraint, words: &[Word]) -> Vec<usize>
+{
+    let compact = constraint.compact();
+    (0..words.len()).filter(|&i| allowed(constraint, compact.as_ref(), &words[i])).collect()
+}
+
+/// Whether `word` satisfies `constraint`, using its compact form if there
+/// is one.
+fn allowed(constraint: &Constraint, compact: Option<&CompactConstraint>, word: &Word) -> bool
+{
+    match compact {
+        Some(compact) if word.ascii().is_some() => compact.allows_fast(word),
+        _ => constraint.allows(word)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::constraint::{narrow_words, wordle_guess, Constraint};
+use crate::solver::WordleSolver;
+use crate::word::Word;
+use std::io::{self, Write};
+use std::time::Duration;
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+// std's Instant panics in the browser.
+#[cfg(feature = "wasm")]
+use web_time::Instant;
+
+/// What `Game::observe` learns about a guess.
+pub enum Observation<'w> {
+    /// The answer itself, so the feedback can be worked out.
+    Answer(&'w Word),
+    /// The feedback wordle showed, with G for green, Y for yellow and X for
+    /// gray, the way `feedback_pattern` writes it.
+    Pattern(&'w str),
+    /// A result row that was parsed already.
+    Row(&'w Constraint)
+}
+
+impl<'w> From<&'w Word> for Observation<'w> {
+    fn from(answer: &'w Word) -> Self
+    {
+        Observation::Answer(answer)
+    }
+}
+
+impl<'w> From<&'w str> for Observation<'w> {
+    fn from(pattern: &'w str) -> Self
+    {
+        Observation::Pattern(pattern)
+    }
+}
+
+impl<'w> From<&'w Constraint> for Observation<'w> {
+    fn from(row: &'w Constraint) -> Self
+    {
+        Observation::Row(row)
+    }
+}
+
+/// One game against a `WordleSolver`, fed a turn at a time: ask it for a
+/// guess with `suggest`, then tell it how the guess did with `observe`.
+pub struct Game<'a> {
+    solver: &'a WordleSolver,
+    constraint: Constraint,
+    /// The answers that fit everything observed so far.
+    remaining: Vec<&'a Word>,
+    guesses: Vec<&'a Word>,
+    /// Whether the last guess was the answer.
+    solved: bool,
+    /// How long the last `suggest` took, and all of them together.
+    suggest_time: Duration,
+    total_suggest_time: Duration,
+    verbose: bool,
+    progress: bool
+}
+
+impl<'a> Game<'a> {
+    /// Start a game where `constraint` is already known about the answer.
+    pub fn new(solver: &'a WordleSolver, constraint: Constraint) -> Self
+    {
+        let remaining = solver.remaining_words(&constraint);
+        Self {
+            solver,
+            constraint,
+            remaining,
+            guesses: Vec::new(),
+            solved: false,
+            suggest_time: Duration::ZERO,
+            total_suggest_time: Duration::ZERO,
+            verbose: false,
+            progress: false
+        }
+    }
+
+    /// Print how many words are left, and which, with every suggestion.
+    pub fn with_verbose(mut self, verbose: bool) -> Self
+    {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Show a progress bar while scoring guesses.
+    pub fn with_progress(mut self, progress: bool) -> Self
+    {
+        self.progress = progress;
+        self
+    }
+
+    /// The best next guess, the same one `WordleSolver::best_guess_excluding`
+    /// would suggest.
+    pub fn suggest(&mut self) -> Result<&'a Word, String>
+    {
+        let started = Instant::now();
+        let guess = self.solver.choose_from(&self.constraint, self.remaining.clone(), &self.guesses,
+            self.verbose, self.progress);
+        self.suggest_time = started.elapsed();
+        self.total_suggest_time += self.suggest_time;
+        guess
+    }
+
+    /// Record that `guess` was played, and what came of it.
+    pub fn observe<'w>(&mut self, guess: &'a Word, observation: impl Into<Observation<'w>>) ->
+            Result<(), String>
+    {
+        let row = match observation.into() {
+            Observation::Answer(answer) => wordle_guess(guess, answer),
+            Observation::Pattern(pattern) => Constraint::from_guess_and_gyx(guess.as_str(), pattern)?,
+            Observation::Row(row) => row.clone()
+        };
+        if row.len() != self.constraint.len() {
+            return Err(format!("The row has {} letters, but the words have {}.", row.len(), self.constraint.len()));
+        }
+        self.solver.merge(&mut self.constraint, &row)?;
+        self.guesses.push(guess);
+        self.solved = row.is_solved();
+        // The words left only ever get fewer, so there's no need to filter
+        // the whole word list again.
+        self.remaining = narrow_words(&self.constraint, self.remaining.drain(..));
+        Ok(())
+    }
+
+    /// Everything observed about the answer so far.
+    pub fn constraint(&self) -> &Constraint
+    {
+        &self.constraint
+    }
+
+    /// The answers that fit everything observed so far.
+    pub fn remaining(&self) -> &[&'a Word]
+    {
+        &self.remaining
+    }
+
+    /// The guesses observed so far, in order.
+    pub fn guesses(&self) -> &[&'a Word]
+    {
+        &self.guesses
+    }
+
+    /// How long the last `suggest` took.
+    pub fn suggest_time(&self) -> Duration
+    {
+        self.suggest_time
+    }
+
+    /// How long every `suggest` so far took together.
+    pub fn total_suggest_time(&self) -> Duration
+    {
+        self.total_suggest_time
+    }
+
+    /// Report on stderr how long the last `suggest` took, and all of them so
+    /// far, after flushing what was printed on stdout.
+    pub fn print_time(&self)
+    {
+        io::stdout().flush().unwrap();
+        eprintln!("Took {:.3}s, {:.3}s in all.", self.suggest_time.as_secs_f64(),
+            self.total_suggest_time.as_secs_f64());
+    }
+
+    /// Whether the last guess was the answer.
+    pub fn is_solved(&self) -> bool
+    {
+        self.solved
+    }
+}
@@ -0,0 +1,46 @@
+//! Find the best (hopefully) next guess when solving a wordle puzzle.
+//!
+//! Everything re-exported below is public API. The modules themselves, and
+//! anything they don't re-export, are implementation details.
+
+mod constraint;
+mod game;
+mod logging;
+mod lru;
+mod matrix;
+mod parallel;
+#[cfg(feature = "plot")]
+mod plot;
+mod scoring;
+#[cfg(feature = "server")]
+mod server;
+mod solver;
+mod summary;
+mod tree;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod word;
+
+pub use constraint::{
+    feedback_pattern, filter_words, wordle_guess, CompactConstraint, Constraint, RowFormat, RowOps
+};
+pub use game::{Game, Observation};
+#[cfg(feature = "plot")]
+pub use plot::plot_histogram;
+pub use scoring::{
+    score_guess_count_eliminations, score_guess_entropy, score_guess_expected, score_guess_minimax,
+    Bucket, Scoring
+};
+#[cfg(feature = "server")]
+pub use server::serve;
+pub use solver::{
+    GameResult, OpenerStats, OpenerStrategy, Tiebreak, Verbosity, WordleSolver, CANDIDATE_LIST_THRESHOLD, DEFAULT_MAX_GUESSES
+};
+pub use summary::{Summary, MAX_GUESSES};
+pub use tree::DecisionTree;
+#[cfg(feature = "wasm")]
+pub use wasm::best_guess_from_constraints;
+pub use word::{
+    check_words, check_words_from_str, position_frequency, read_weights, read_words, read_words_from_str,
+    DictionaryCheck, WeightList, Word, WordList
+};
@@ -0,0 +1,26 @@
+//! The `log` macros with the `log` feature, and macros that do nothing
+//! without it, so the rest of the crate can log either way.
+
+#[cfg(feature = "log")]
+pub(crate) use log::{debug, info};
+
+#[cfg(not(feature = "log"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
+
+#[cfg(not(feature = "log"))]
+pub(crate) use {debug, info};
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A map that holds at most `capacity` entries, and forgets the least
+/// recently used one to make room for a new one.
+pub(crate) struct Lru<K, V> {
+    capacity: usize,
+    /// Each value, with when it was last used.
+    entries: HashMap<K, (V, u64)>,
+    clock: u64
+}
+
+impl<K: Eq + Hash, V> Lru<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self
+    {
+        Self { capacity, entries: HashMap::new(), clock: 0 }
+    }
+
+    pub(crate) fn get(&mut self, key: &K) -> Option<&V>
+    {
+        self.clock += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(value)
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V)
+    {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            // The capacity is small, so finding the oldest entry is cheap.
+            if let Some(oldest) = self.entries.values().map(|(_value, used)| *used).min() {
+                self.entries.retain(|_key, (_value, used)| *used != oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (value, self.clock));
+    }
+}
@@ -1,418 +1,1126 @@
-use clap::Parser;
-use dirs;
-use hex;
-use indicatif::{ParallelProgressIterator, ProgressStyle};
-use rayon::prelude::*;
-use serde_json;
-use sha2::{Sha256, Digest};
-use std::{cmp, fs};
-use std::collections::{HashSet, HashMap};
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::str::Chars;
-use std::sync::Mutex;
+use clap::error::ErrorKind;
+use clap::{ArgAction, ArgGroup, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+use wordle_solve::{
+    check_words, check_words_from_str, feedback_pattern, position_frequency, read_weights, read_words, read_words_from_str, Constraint,
+    DecisionTree, Game, GameResult, OpenerStrategy, RowFormat, RowOps, Scoring, Tiebreak, Verbosity, WeightList, Word, WordList, WordleSolver, CANDIDATE_LIST_THRESHOLD,
+    DEFAULT_MAX_GUESSES
+};
 
 #[derive(Parser)]
 /// Print out the next best (hopefully) guess when solving a wordle puzzle.
 ///
 /// Each constraint describes a single wordle result row. Put a - in front of
 /// each character that is gray, a ~ in front of each character that is yellow,
-/// and leave the green ones as is.
+/// and leave the green ones as is. A letter with a ! in front isn't anywhere
+/// in the word, e.g. "!q !z", and "=2e" says there are exactly two e's.
 ///
 /// Example: wordle-solve -- "-r -a ~i -s -e" "-h -o ~t -l y"
 #[command(author, version, about)]
 struct Cli {
-    #[arg(short, long, value_name = "FILE")]
-    words: Option<String>,
-    /// One or more wordle result rows.
-    constraint: Vec<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Without a command, the arguments of `solve`.
+    #[command(flatten)]
+    solve: SolveArgs,
+    #[command(flatten)]
+    options: Options
+}
+
+/// What to do. Each command takes only the arguments that make sense for it.
+#[derive(Subcommand)]
+enum Command {
+    /// Suggest the next guess for the result rows so far. This is what runs
+    /// when no command is given.
+    Solve(SolveArgs),
+    /// Keep suggesting guesses, reading each result row from stdin.
+    Interactive(RowArgs),
     /// See how the algorithm performs against the given word.
-    #[arg(short, long, value_name = "TEST")]
-    test: Option<String>,
+    Test {
+        word: String
+    },
+    /// Check a game: that every guess is in the word list, and that the last
+    /// one is the answer.
+    Verify {
+        #[arg(required = true, value_name = "GUESS")]
+        path: Vec<String>
+    },
+    /// Play Antiwordle against the given word, trying to last as long as
+    /// possible before guessing it.
+    Anti {
+        word: String
+    },
+    /// Pick a random answer and show how the algorithm finds it. Use --seed
+    /// to pick the same answer every time.
+    Simulate {
+        /// Pick the answer with this seed instead of --seed, which leaves
+        /// ties between guesses alone.
+        #[arg(long, value_name = "N")]
+        answer_seed: Option<u64>
+    },
+    /// See how many guesses the algorithm needs against an adversary that
+    /// keeps as many words possible for as long as it can, like Absurdle.
+    Absurdle,
+    /// Solve two boards at once, Dordle style. Each argument holds the result
+    /// rows for one board, separated by commas.
+    Dordle {
+        #[arg(num_args = 2, required = true, value_names = ["ROWS", "ROWS"], allow_hyphen_values = true)]
+        boards: Vec<String>
+    },
     /// See how the algorithm performs against every word.
-    #[arg(long)]
-    full_test: bool
+    #[command(visible_alias = "bench")]
+    FullTest {
+        /// Also write every game to this CSV file.
+        #[arg(long, value_name = "FILE")]
+        csv: Option<String>,
+        /// Only play the games for the answers in this file, rather than
+        /// every word in the word list.
+        #[arg(long, value_name = "FILE")]
+        answers_file: Option<String>,
+        /// Print only a SHA-256 digest of how many guesses each game took,
+        /// to spot any change in how the solver plays.
+        #[arg(long)]
+        digest: bool,
+        /// Also draw the histogram as a bar chart in this SVG file.
+        #[cfg(feature = "plot")]
+        #[arg(long, value_name = "FILE")]
+        plot: Option<String>
+    },
+    /// Run full-test with every way of scoring guesses, and compare them.
+    CompareScorers,
+    /// Measure how many guesses a second the eliminations scoring gets
+    /// through against every answer, on all the threads.
+    BenchScoring {
+        /// Keep scoring for at least this many seconds.
+        #[arg(long, default_value_t = 5.0)]
+        seconds: f64
+    },
+    /// Print the SHA-256 hash of the word list, to check that two machines
+    /// use the same one.
+    DictHash,
+    /// Check the word list for problems, e.g. words of different lengths,
+    /// and report all of them. Exits with an error if it can't be used.
+    CheckDictionary,
+    /// Print the feedback GUESS gets when the answer is ANSWER, with G for
+    /// green, Y for yellow and X for gray.
+    Feedback {
+        guess: String,
+        answer: String
+    },
+    /// Work out the best first guess from scratch, ignoring the cache, and
+    /// save it in the cache.
+    BestOpener,
+    /// Play the first guess against every answer, and print how many
+    /// answers are left afterwards: on average, at most, and how often
+    /// each number comes up.
+    OpenerStats,
+    /// Print the N lowest scoring first guesses with their scores.
+    WorstOpeners {
+        n: usize
+    },
+    /// Score every first guess, and write them to a CSV file with their
+    /// scores, best first.
+    OpenerScores {
+        #[arg(value_name = "FILE")]
+        path: String
+    },
+    /// Work out the best guess for every possible game and save them in the
+    /// cache, so later runs don't have to score anything.
+    Precompute,
+    /// Answer POST requests with a JSON list of result rows with the best
+    /// guess for them, as JSON.
+    #[cfg(feature = "server")]
+    Serve {
+        port: u16,
+        /// The address to listen on. The default only takes requests from
+        /// this machine; 0.0.0.0 takes them from anywhere.
+        #[arg(long, value_name = "ADDRESS", default_value = "127.0.0.1")]
+        bind: String
+    }
 }
 
-#[derive(Clone)]
-#[derive(Debug)]
-struct CharacterConstraint {
-    is: Option<char>,
-    is_not: HashSet<char>
+/// The result rows of the game so far.
+#[derive(Args)]
+struct RowArgs {
+    /// One or more wordle result rows.
+    constraint: Vec<String>,
+    /// How the result rows are written.
+    #[arg(long, value_enum, default_value_t = RowFormat::Prefix)]
+    format: RowFormat,
+    /// The characters that mark gray and yellow letters, and optionally
+    /// green ones, in prefix rows, e.g. "X Y .". Without it, - and ~.
+    #[arg(long, value_name = "MARKERS")]
+    ops: Option<RowOps>,
+    /// A guess and the colored squares wordle showed for it, e.g.
+    /// "crane:⬛🟨🟩⬛⬛". May be given more than once.
+    #[arg(long, value_name = "GUESS:SQUARES")]
+    pattern: Vec<String>,
+    /// Start from the rows saved earlier with --save-state.
+    #[arg(long, value_name = "FILE")]
+    load_state: Option<String>,
+    /// Letters that are given before the first guess, with _ for the ones
+    /// that aren't, e.g. "_ r _ _ _".
+    #[arg(long, value_name = "LETTERS")]
+    given: Option<String>
 }
 
-#[derive(Debug)]
-struct Constraint {
-    character: Vec<CharacterConstraint>,
-    // For each char, track how many there are at least in the word.
-    min_occurrence: HashMap<char, usize>,
-    max_occurrence: HashMap<char, usize>
+#[derive(Args)]
+struct SolveArgs {
+    #[command(flatten)]
+    rows: RowArgs,
+    /// Save everything the rows (and --load-state) say about the answer, to
+    /// pick up the game again later.
+    #[arg(long, value_name = "FILE")]
+    save_state: Option<String>,
+    /// Print what the result rows say about the answer before guessing.
+    #[arg(long)]
+    show_constraint: bool,
+    #[command(flatten)]
+    output: OutputArgs
 }
 
-impl Constraint {
-    pub fn new(size: usize) -> Self {
-        let character = vec![CharacterConstraint {is: None, is_not: HashSet::new() }; size];
-        Self {
-            character,
-            min_occurrence: HashMap::new(),
-            max_occurrence: HashMap::new()
-        }
-    }
+/// What to print instead of the best guess. Only one of these at a time.
+#[derive(Args)]
+#[command(group(ArgGroup::new("output").multiple(false)))]
+struct OutputArgs {
+    /// Read a whole game from a file, with each guess on its own line
+    /// followed by its colored squares, and list the words it leaves.
+    #[arg(long, value_name = "FILE", group = "output")]
+    share: Option<String>,
+    /// Print every word that's still possible instead of a guess.
+    #[arg(long, group = "output")]
+    list: bool,
+    /// Print the most common letters in each position among the words that
+    /// are still possible.
+    #[arg(long, group = "output")]
+    heatmap: bool,
+    /// Show why the best guess won: its score, the runners-up, and how it
+    /// splits up the remaining words.
+    #[arg(long, group = "output")]
+    explain: bool,
+    /// Print the N best guesses and their scores instead of just the best one.
+    #[arg(long, value_name = "N", group = "output")]
+    top: Option<usize>,
+    /// Print the guesses that are sure to win on the turn after them,
+    /// whichever word is the answer.
+    #[arg(long, group = "output")]
+    winning: bool
+}
 
-    pub fn from_string(string: &String, size: usize) -> Self {
-        let mut constraint = Constraint::new(size);
-        let mut i = 0;
-        enum Op {
-            Green,
-            Yellow,
-            Gray
-        }
-        let mut op = Op::Green;
-        let mut count: HashMap<char, usize> = HashMap::new();
-        let mut found_max = HashSet::new();
-        for c in string.chars() {
-            match c {
-                ' ' => { i += 1; op = Op::Green; },
-                '-' => op = Op::Gray,
-                '~' => op = Op::Yellow,
-                x => {
-                    match op {
-                        Op::Green => {
-                            constraint.character[i].is = Some(x);
-                            constraint.increment_min_occurrence(&x);
-                            count.entry(x).and_modify(|x| *x += 1).or_insert(1);
-                        },
-                        Op::Yellow => {
-                            constraint.character[i].is_not.insert(x);
-                            constraint.increment_min_occurrence(&x);
-                            count.entry(x).and_modify(|x| *x += 1).or_insert(1);
-                        },
-                        Op::Gray => {
-                            constraint.character[i].is_not.insert(x);
-                            found_max.insert(x);
-                            ()
-                        }
-                    }
-                }
-            }
-        }
-        for c in found_max {
-            constraint.max_occurrence.insert(c, *count.get(&c).unwrap_or(&0));
-        }
-        constraint
+impl SolveArgs {
+    /// Whether any of the arguments were given on the command line.
+    fn is_given(&self) -> bool
+    {
+        let rows = &self.rows;
+        let output = &self.output;
+        !rows.constraint.is_empty() || rows.format != RowFormat::Prefix || rows.ops.is_some() ||
+                !rows.pattern.is_empty() || rows.load_state.is_some() || rows.given.is_some() ||
+                self.save_state.is_some() || self.show_constraint || output.share.is_some() || output.list ||
+                output.heatmap || output.explain || output.top.is_some() || output.winning
     }
+}
 
-    pub fn increment_min_occurrence(&mut self, c: &char) {
-        self.min_occurrence.entry(*c).and_modify(|n| *n += 1).or_insert(1);
-    }
+/// How the solver plays, for every command.
+#[derive(Args)]
+struct Options {
+    /// The word list, one word per line. Use - to read it from stdin.
+    #[arg(short, long, value_name = "FILE", global = true)]
+    words: Option<String>,
+    /// Only use words of this length, skipping any others in the word lists.
+    /// By default all words must be as long as the first one.
+    #[arg(long, value_name = "N", global = true)]
+    word_length: Option<usize>,
+    /// Words that may be guessed, if different from the possible answers in
+    /// the word list.
+    #[arg(long, value_name = "FILE", global = true)]
+    guesses: Option<String>,
+    /// How likely each answer is, one word,weight pair per line. Words that
+    /// aren't listed have a weight of 1.
+    #[arg(long, value_name = "FILE", global = true)]
+    weights: Option<String>,
+    /// Print more about what is going on: -v for how many words are left
+    /// and every game in full-test, -vv for the words themselves when there
+    /// are few, and -vvv for every word's score. Ignored with --json.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Don't show progress bars or how many words are left, only the
+    /// result.
+    #[arg(long, global = true)]
+    quiet: bool,
+    /// Report on stderr how long picking each guess took.
+    #[arg(long, global = true)]
+    time: bool,
+    /// Check every time rows are merged that the result allows exactly the
+    /// words that all of the rows do. Slow, so only for finding bugs.
+    #[arg(long, global = true)]
+    strict: bool,
+    /// Print results as JSON.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Only guess words that satisfy all revealed constraints.
+    #[arg(long, global = true)]
+    hard: bool,
+    /// Once N words or fewer are left, only guess words that might be the
+    /// answer.
+    #[arg(long, value_name = "N", default_value_t = 0, global = true)]
+    endgame_threshold: usize,
+    /// Give up on a game in test, full-test or absurdle after this many guesses.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_GUESSES, global = true)]
+    max_guesses: usize,
+    /// Never suggest a word that was already guessed, in test and
+    /// interactive.
+    #[arg(long, global = true)]
+    exclude_guessed: bool,
+    /// Always follow the first guess with the same second guess, whatever
+    /// feedback it got. Both are cached, so the first two turns are instant.
+    #[arg(long, global = true)]
+    opening_pair: bool,
+    /// Precompute the feedback of every guess against every answer. Much
+    /// faster, but needs a lot of memory for big word lists.
+    #[arg(long, global = true)]
+    feedback_matrix: bool,
+    /// Pick between equally good guesses at random, using this seed. Without
+    /// it, ties go to the alphabetically last word.
+    #[arg(long, value_name = "N", global = true)]
+    seed: Option<u64>,
+    /// When only a few words are left, look this many guesses ahead to
+    /// find the guess that needs the fewest guesses on average.
+    #[arg(long, value_name = "D", default_value_t = 1, global = true)]
+    depth: usize,
+    /// How to pick between equally good guesses. The default is alpha, or
+    /// random with --seed.
+    #[arg(long, value_enum, global = true)]
+    tiebreak: Option<Tiebreak>,
+    /// While N or more words are left, score guesses by how common their
+    /// letters are in each position, which is much faster but not as good.
+    #[arg(long, value_name = "N", global = true)]
+    exact_below: Option<usize>,
+    /// How to pick the first guess: score it like any other, take the word
+    /// with the most different vowels, or always play one word, e.g.
+    /// word:crane.
+    #[arg(long, value_name = "score|vowels|word:WORD", default_value_t = OpenerStrategy::Score, global = true)]
+    opener_strategy: OpenerStrategy,
+    /// Don't read or write the cache of guesses worked out in earlier runs.
+    #[arg(long, global = true)]
+    no_cache: bool,
+    /// Keep the cache in this file instead of the user's cache directory.
+    #[arg(long, value_name = "FILE", global = true, conflicts_with = "no_cache")]
+    cache_path: Option<String>,
+    /// Use at most this many threads. 0, the default, uses one per CPU.
+    #[arg(long, value_name = "N", global = true)]
+    threads: Option<usize>,
+    /// How to score candidate guesses.
+    #[arg(long, value_enum, default_value_t = Scoring::Eliminations, global = true)]
+    scoring: Scoring
+}
 
-    pub fn update(&mut self, constraint: &Constraint) {
-        for (c, count) in constraint.min_occurrence.iter() {
-            self.min_occurrence.entry(*c)
-                    .and_modify(|v| *v = cmp::max(*v, *count))
-                    .or_insert(*count);
-        }
-        for (c, count) in constraint.max_occurrence.iter() {
-            self.max_occurrence.entry(*c)
-                    .and_modify(|v| *v = cmp::min(*v, *count))
-                    .or_insert(*count);
-        }
-        for (my_c, other_c) in self.character.iter_mut().zip(constraint.character.iter()) {
-            if other_c.is == None {
-                for c in other_c.is_not.iter() {
-                    my_c.is_not.insert(*c);
-                }
-            } else {
-                my_c.is = other_c.is;
-            }
+/// Return the value in `result`, or exit with the error message.
+fn or_exit<T>(result: Result<T, String>) -> T
+{
+    match result {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
         }
     }
-
-    pub fn allows(&self, word: &Word) -> bool
-    {
-        self.min_occurrence.iter()
-                .all(|(key, value)| word.char_count(key) >= *value) &&
-        self.max_occurrence.iter()
-                .all(|(key, value)| word.char_count(key) <= *value) &&
-        // Check that green letters are where they should be.
-        self.character.iter().zip(word.chars())
-                .all(|(cc, y)|
-                        match cc.is {
-                            None => true,
-                            Some(x) => x == y
-                        }) &&
-        self.character.iter().zip(word.chars())
-                .all(|(cc, y)| !cc.is_not.contains(&y))
-    }
 }
 
-#[derive(Clone)]
-#[derive(PartialEq, Eq)]
-struct Word {
-    word: String,
-    char_frequency: HashMap<char, usize>
+/// Read a word list, warning about any words that had to be skipped or
+/// were duplicates.
+fn read_words_or_exit(path: &str, word_length: Option<usize>) -> WordList
+{
+    warn_about_words(or_exit(read_words(path, word_length)), path, word_length)
 }
 
-impl Ord for Word{
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.word.cmp(&other.word)
+/// Warn about any words that were left out of `word_list`, which came from
+/// `path`, the same way for every word list.
+fn warn_about_words(word_list: WordList, path: &str, word_length: Option<usize>) -> WordList
+{
+    if word_list.skipped > 0 {
+        eprintln!("Skipped {} words in {} that aren't {} letters long.",
+            word_list.skipped, path, word_length.unwrap());
     }
-}
-
-impl PartialOrd for Word{
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        self.word.partial_cmp(&other.word)
+    if word_list.duplicates > 0 {
+        eprintln!("Left out {} duplicate words in {}.", word_list.duplicates, path);
     }
+    word_list
 }
 
-impl Word {
-    pub fn new(word: String) -> Self
-    {
-        let char_frequency = char_frequency(word.chars());
-        Self { word, char_frequency }
-    }
+/// The word list that's used without --words, if it exists.
+const DEFAULT_WORDS: &str = "words";
 
-    pub fn char_count(&self, c: &char) -> usize
-    {
-        *self.char_frequency.get(c).unwrap_or(&0)
-    }
+/// The word list that's used if there's no --words and no DEFAULT_WORDS
+/// file. It's the same list, so the cache works the same for both.
+const BUILTIN_WORDS: &str = include_str!("../words");
 
-    pub fn chars(&self) -> Chars<'_>
-    {
-        self.word.chars()
-    }
+/// The first part of every cache key. Bump it when the meaning of keys or
+/// entries changes, so older entries are ignored.
+const CACHE_VERSION: &str = "v4";
 
-    pub fn len(&self) -> usize
-    {
-        self.word.len()
+/// Everything about `options` that changes which guesses the solver makes,
+/// for the cache key.
+fn strategy(options: &Options) -> String
+{
+    let mut strategy = options.scoring.to_possible_value().unwrap().get_name().to_string();
+    if options.hard {
+        strategy += ",hard";
+    }
+    if options.endgame_threshold > 0 {
+        strategy += &format!(",endgame={}", options.endgame_threshold);
     }
+    if options.depth > 1 {
+        strategy += &format!(",depth={}", options.depth);
+    }
+    if let Some(tiebreak) = options.tiebreak {
+        strategy += &format!(",tiebreak={}", tiebreak.to_possible_value().unwrap().get_name());
+    }
+    if let Some(seed) = options.seed {
+        strategy += &format!(",seed={}", seed);
+    }
+    if let Some(exact_below) = options.exact_below {
+        strategy += &format!(",exact-below={}", exact_below);
+    }
+    if options.opener_strategy != OpenerStrategy::Score {
+        strategy += &format!(",opener={}", options.opener_strategy);
+    }
+    strategy
 }
 
-fn char_frequency(chars: Chars) -> HashMap<char, usize>
+/// Write guesses with their scores to a CSV file at `path`.
+fn write_scores(path: &str, scores: &[(&Word, f64)]) -> Result<(), String>
 {
-    let mut char_frequency = HashMap::new();
-    for c in chars {
-        char_frequency.entry(c).and_modify(|n| *n += 1).or_insert(1);
+    let write_error = |error: io::Error| format!("Failed to write {}: {}", path, error);
+    let mut csv = BufWriter::new(File::create(path)
+            .map_err(|error| format!("Failed to create {}: {}", path, error))?);
+    writeln!(csv, "word,score").map_err(write_error)?;
+    for (guess, score) in scores {
+        writeln!(csv, "{},{}", guess.as_str(), score).map_err(write_error)?;
     }
-    char_frequency
+    csv.flush().map_err(write_error)
 }
 
-fn wordle_guess(guess: &Word, answer: &Word) -> Constraint
+/// Print guesses with their scores, one per line or as a JSON list.
+fn print_scores(scores: &[(&Word, f64)], json: bool)
 {
-    let mut constraint: Constraint = Constraint::new(guess.len());
-    for (i, (g, a)) in guess.chars().zip(answer.chars()).enumerate() {
-        if g == a {
-            constraint.character[i].is = Some(g);
-        } else {
-            constraint.character[i].is_not.insert(g);
+    if json {
+        let scores: Vec<_> = scores.iter()
+                .map(|(guess, score)| json!({"guess": guess.as_str(), "score": score}))
+                .collect();
+        println!("{}", json!(scores));
+    } else {
+        for (guess, score) in scores {
+            println!("{}  {}", guess.as_str(), score);
         }
     }
+}
 
-    let guess_frequency = char_frequency(guess.chars());
+/// How many letters --heatmap shows for each position.
+const HEATMAP_LETTERS: usize = 5;
 
-    for (c, guess_count) in guess_frequency.iter() {
-        let answer_count = answer.char_count(c);
-        let min_count = cmp::min(*guess_count, answer_count);
-        if min_count > 0 {
-            constraint.min_occurrence.insert(*c, min_count);
-        }
-        if *guess_count > answer_count {
-            constraint.max_occurrence.insert(*c, answer_count);
-        }
-    }
+/// How many runners-up --explain shows.
+const EXPLAIN_RUNNERS_UP: usize = 5;
 
-    constraint
-}
+/// How many other winning guesses --winning shows.
+const WINNING_OTHERS: usize = 5;
 
-fn filter_words<'a>(constraint: &Constraint, words: &'a Vec<Word>) -> Vec<&'a Word>
+/// Print the best guess with its score, the next best guesses, and how the
+/// best guess splits up the remaining words.
+fn explain(solver: &WordleSolver, constraint: &Constraint, json: bool)
 {
-    let mut v = Vec::new();
+    let guess = or_exit(solver.best_guess(constraint, false));
+    let ranked = or_exit(solver.ranked_guesses(constraint, usize::MAX));
+    let score = ranked.iter().find(|(g, _)| *g == guess).map_or(0.0, |(_, score)| *score);
+    let runners_up: Vec<_> = ranked.iter()
+            .filter(|(g, _)| *g != guess)
+            .take(EXPLAIN_RUNNERS_UP)
+            .collect();
 
-    for word in words {
-        if constraint.allows(word) {
-            v.push(word);
-        }
+    let remaining = solver.remaining_words(constraint);
+    let mut buckets: Vec<(String, usize)> = WordleSolver::partition(guess, &remaining).into_iter()
+            .map(|(pattern, words)| (pattern, words.len()))
+            .collect();
+    buckets.sort_by(|(p_a, n_a), (p_b, n_b)| n_b.cmp(n_a).then(p_a.cmp(p_b)));
+
+    if json {
+        let runners_up: Vec<_> = runners_up.iter()
+                .map(|(g, score)| json!({"guess": g.as_str(), "score": score}))
+                .collect();
+        let buckets: Vec<_> = buckets.iter()
+                .map(|(pattern, n)| json!({"pattern": pattern, "words": n}))
+                .collect();
+        println!("{}", json!({
+            "best_guess": guess.as_str(),
+            "score": score,
+            "runners_up": runners_up,
+            "buckets": buckets
+        }));
+        return;
     }
 
-    v
+    println!("Best guess: {}  {}", guess.as_str(), score);
+    println!("Runners-up:");
+    for (g, score) in runners_up {
+        println!("  {}  {}", g.as_str(), score);
+    }
+    println!("{} splits the {} remaining words into {} groups:", guess.as_str(), remaining.len(), buckets.len());
+    for (pattern, n) in buckets {
+        println!("  {}  {}", pattern, n);
+    }
 }
 
-fn score_guess_count_eliminations(guess: &Word, words: &Vec<&Word>, constraint: &Constraint) -> usize
+/// Where the cache goes without --cache-path: in the user's cache directory.
+#[cfg(feature = "cache")]
+fn default_cache_path() -> Option<PathBuf>
 {
-    let mut score = words.len() * words.len();
-    for answer in words {
-        // If the word is `word`, then how good is this guess?
-        let mut answer_constraint = wordle_guess(guess, answer);
-        answer_constraint.update(&constraint);
-        score -= words.iter().filter(|w| answer_constraint.allows(w)).count();
-    }
-    score
+    dirs::cache_dir().map(|dir| dir.join("wordle-solve.cache"))
 }
 
-fn read_words(path: &String) -> Result<(Vec<Word>, String), String>
+/// Built without the cache feature, there's only a cache with --cache-path.
+#[cfg(not(feature = "cache"))]
+fn default_cache_path() -> Option<PathBuf>
 {
-    let mut words = Vec::new();
-    let mut hasher = Sha256::new();
-
-    let file = match File::open(path) {
-        Ok(file) => file,
-        Err(error) => panic!("Failed to open {}: {:?}", path, error)
-    };
-
-    let mut word_length = None;
-    for line_result in io::BufReader::new(file).lines() {
-        let line = line_result.unwrap();
-        let l1 = line.chars().count();
-        match word_length {
-            Some(l2) => if l1 != l2 {
-                return Err(format!("Some lines in {} contain {} characters while others contain {} characters (e.g. {}).",
-                    path, l1, l2, line));
-            },
-            None => word_length = Some(l1)
-        }
-        hasher.update(&line);
-        words.push(Word::new(line));
-    }
-    Ok((words, hex::encode(hasher.finalize())))
+    None
 }
 
-struct WordleSolver {
-    words: Vec<Word>,
-    first_guess: Mutex<Option<usize>>
+/// Read a constraint saved with --save-state.
+fn load_state(path: &str, word_length: usize) -> Result<Constraint, String>
+{
+    let state = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+    let constraint: Constraint = serde_json::from_str(&state)
+            .map_err(|error| format!("Failed to parse {}: {}", path, error))?;
+    if constraint.len() != word_length {
+        return Err(format!("{} is for {} letter words, but the word list has {} letters.",
+            path, constraint.len(), word_length));
+    }
+    Ok(constraint)
 }
 
-impl WordleSolver {
-    fn best_guess<'a>(&'a self, constraint: &Constraint, verbose: bool) ->
-            Result<&'a Word, String>
-    {
-        let remaining_words = filter_words(constraint, &self.words);
-
-        if remaining_words.len() == self.words.len() {
-            let first_guess = self.first_guess.lock().unwrap();
-            if let Some(index) = *first_guess {
-                return Ok(&self.words[index]);
-            }
+/// Suggest a guess, read the row wordle showed for it from stdin, and repeat
+/// until only one word is left or the user types quit.
+fn interactive(solver: &WordleSolver, constraint: Constraint, rows: &RowArgs, word_length: usize,
+        time: bool)
+{
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut game = Game::new(solver, constraint).with_verbose(true).with_progress(true);
+    loop {
+        let guess = or_exit(game.suggest());
+        let solved = game.remaining().len() == 1;
+        if solved {
+            println!("The answer is {}", guess.as_str());
+        } else {
+            println!("Best guess: {}", guess.as_str());
         }
-
-        if remaining_words.len() < 1 {
-            return Err("Error: No words match those constraints.".to_string());
+        if time {
+            game.print_time();
         }
-        if remaining_words.len() == 1 {
-            return Ok(remaining_words.first().unwrap());
+        if solved {
+            return;
         }
-        if verbose {
-            println!("{}/{} words remaining", remaining_words.len(), self.words.len());
-            if remaining_words.len() < 15 {
-                for w in &remaining_words {
-                    println!("  {}", w.word)
-                }
+
+        loop {
+            print!("> ");
+            io::stdout().flush().unwrap();
+            let line = match lines.next() {
+                Some(Ok(line)) => line,
+                _ => return
+            };
+            let line = line.trim();
+            if line == "quit" {
+                return;
+            }
+            // The guess is known, so it can be left out of the row.
+            let line = if rows.format != RowFormat::Prefix && !line.contains(char::is_whitespace) {
+                format!("{} {}", guess.as_str(), line)
+            } else {
+                line.to_string()
+            };
+            match parse_row(&line, rows, word_length) {
+                Ok(row) => {
+                    or_exit(game.observe(guess, &row));
+                    break;
+                },
+                Err(error) => eprintln!("{}", error)
             }
         }
+    }
+}
 
-        if remaining_words.len() == 2 {
-            return Ok(remaining_words.first().unwrap());
-        }
+/// Run a full test with each scorer in turn, reusing `solver` and its
+/// feedback matrix, and print a table of the results.
+fn compare_scorers(mut solver: WordleSolver, options: &Options)
+{
+    let mut results = Vec::new();
+    for scoring in Scoring::value_variants() {
+        solver = solver.with_scoring(*scoring);
+        let summary = solver.full_test(options.verbose > 0);
+        results.push((scoring.to_possible_value().unwrap().get_name().to_string(), summary));
+    }
 
-        let style = ProgressStyle::with_template("{bar:60} {pos}/{len} {eta}").unwrap();
-
-        let (_best_score, best_guess, index) =
-            self.words
-                    .par_iter()
-                    .progress_with_style(style)
-                    .map(|guess| (score_guess_count_eliminations(guess, &remaining_words, constraint), guess))
-                    // Prefer words that might be the answer.
-                    .map(|(score, guess)|
-                        (score + if constraint.allows(guess) { 1 } else { 0 }, guess))
-                    .enumerate()
-                    .map(|(index, (score, guess))| (score, guess, index))
-                    .max()
-                    .unwrap();
-
-        if remaining_words.len() == self.words.len() {
-            let mut first_guess = self.first_guess.lock().unwrap();
-            *first_guess = Some(index);
-        }
+    if options.json {
+        let results: Vec<_> = results.iter()
+                .map(|(name, summary)| json!({
+                    "scoring": name,
+                    "mean": summary.mean(),
+                    "max": summary.max(),
+                    "failures": summary.failures()
+                }))
+                .collect();
+        println!("{}", json!(results));
+        return;
+    }
+    println!("{:<14} {:>6} {:>4} {:>9}", "Scoring", "Mean", "Max", "Failures");
+    for (name, summary) in results {
+        println!("{:<14} {:>6.3} {:>4} {:>9}", name, summary.mean(), summary.max(), summary.failures());
+    }
+}
 
-        Ok(best_guess)
+/// Merge the rows given on the command line, and the state they start from,
+/// into one constraint.
+fn read_rows(solver: &WordleSolver, rows: &RowArgs, word_length: usize) -> Constraint
+{
+    let mut constraint_acc = match &rows.load_state {
+        Some(path) => or_exit(load_state(path, word_length)),
+        None => Constraint::new(word_length)
+    };
+    if let Some(given) = &rows.given {
+        or_exit(solver.merge(&mut constraint_acc, &or_exit(Constraint::from_given(given, word_length))));
+    }
+    for constraint_string in &rows.constraint {
+        let constraint = or_exit(parse_row(constraint_string, rows, word_length));
+        or_exit(solver.merge(&mut constraint_acc, &constraint));
+    }
+    for pattern in &rows.pattern {
+        let constraint = match pattern.split_once(':') {
+            Some((guess, squares)) => Constraint::from_guess_and_pattern(guess, squares),
+            None => Err(format!("Pattern '{}' should look like GUESS:SQUARES.", pattern))
+        };
+        or_exit(solver.merge(&mut constraint_acc, &or_exit(constraint)));
     }
+    constraint_acc
+}
 
-    fn test<'a>(&'a self, answer: &Word, verbose: bool) -> Vec<&'a Word>
-    {
-        let mut result = Vec::new();
-        let word_length = self.words.first().unwrap().len();
-        let mut constraint = Constraint::new(word_length);
-        for _ in 1..100 {
-            let guess = self.best_guess(&constraint, false).unwrap();
-            result.push(guess);
-            if verbose {
-                println!("Guess: {}", guess.word);
-            }
-            if guess.word.eq(&answer.word) {
-                return result;
-            }
-            let guess_constraint = wordle_guess(&guess, answer);
-            constraint.update(&guess_constraint);
+/// Parse one result row, written the way `rows` says.
+fn parse_row(row: &str, rows: &RowArgs, word_length: usize) -> Result<Constraint, String>
+{
+    match (&rows.ops, rows.format) {
+        (Some(ops), RowFormat::Prefix) => Constraint::from_string_with_ops(row, word_length, ops),
+        (_, format) => Constraint::from_row(row, format, word_length)
+    }
+}
+
+/// Exit if the rows can't all be true at once.
+fn check_contradiction(constraint: &Constraint)
+{
+    if let Some(reason) = constraint.contradiction() {
+        eprintln!("Error: The rows contradict each other: {}", reason);
+        process::exit(1);
+    }
+}
+
+/// Suggest a guess, or whatever else `args` asks for, for the rows in `args`.
+fn solve(solver: &WordleSolver, args: &SolveArgs, options: &Options, word_length: usize)
+{
+    let mut constraint_acc = read_rows(solver, &args.rows, word_length);
+    let output = &args.output;
+    if let Some(path) = &output.share {
+        let share = or_exit(fs::read_to_string(path)
+                .map_err(|error| format!("Failed to read {}: {}", path, error)));
+        let constraint = or_exit(Constraint::from_share(&share));
+        if constraint.len() != word_length {
+            eprintln!("The guesses in {} have {} letters, but the word list has {}.",
+                path, constraint.len(), word_length);
+            process::exit(1);
         }
-        return result;
+        or_exit(solver.merge(&mut constraint_acc, &constraint));
+    }
+    check_contradiction(&constraint_acc);
+    if let Some(path) = &args.save_state {
+        or_exit(serde_json::to_string(&constraint_acc)
+                .map_err(|error| error.to_string())
+                .and_then(|state| fs::write(path, state)
+                        .map_err(|error| format!("Failed to write {}: {}", path, error))));
+    }
+    if args.show_constraint {
+        println!("{}", constraint_acc);
     }
 
-    fn full_test(&self)
-    {
-        let mut result = HashMap::new();
-        for word in &self.words {
-            let guesses = self.test(&word, false);
-            print!("Guessed {} from", word.word);
-            let count = guesses.len();
-            result.entry(count).and_modify(|c| *c += 1).or_insert(1);
-            for guess in guesses {
-                print!(" {}", guess.word);
+    if output.list || output.share.is_some() {
+        let mut remaining = solver.remaining_words(&constraint_acc);
+        remaining.sort();
+        let remaining: Vec<&str> = remaining.iter().map(|w| w.as_str()).collect();
+        if options.json {
+            println!("{}", json!(remaining));
+        } else {
+            for word in remaining {
+                println!("{}", word);
+            }
+        }
+    } else if output.heatmap {
+        let remaining = solver.remaining_words(&constraint_acc);
+        let heatmap: Vec<Vec<(char, usize)>> = position_frequency(&remaining).into_iter()
+                .map(|counts| {
+                    let mut counts: Vec<(char, usize)> = counts.into_iter().collect();
+                    counts.sort_by(|(c_a, n_a), (c_b, n_b)| n_b.cmp(n_a).then(c_a.cmp(c_b)));
+                    counts.truncate(HEATMAP_LETTERS);
+                    counts
+                })
+                .collect();
+        if options.json {
+            println!("{}", json!(heatmap));
+        } else {
+            for (i, counts) in heatmap.iter().enumerate() {
+                let counts: Vec<String> = counts.iter().map(|(c, n)| format!("{} {:>4}", c, n)).collect();
+                println!("{}: {}", i + 1, counts.join("  "));
             }
-            println!();
         }
+    } else if output.explain {
+        explain(solver, &constraint_acc, options.json);
+    } else if let Some(n) = output.top {
+        print_scores(&or_exit(solver.ranked_guesses(&constraint_acc, n)), options.json);
+    } else if output.winning {
+        let winning = solver.winning_guesses(&solver.remaining_words(&constraint_acc));
+        if options.json {
+            let winning: Vec<&str> = winning.iter().map(|w| w.as_str()).collect();
+            println!("{}", json!(winning));
+        } else if let Some((guess, others)) = winning.split_first() {
+            println!("Guess {} and you win next turn, whatever the answer is.", guess.as_str());
+            if !others.is_empty() {
+                let examples: Vec<&str> = others.iter().take(WINNING_OTHERS).map(|w| w.as_str()).collect();
+                println!("So {} {} other guess{}, e.g. {}.", if others.len() == 1 { "does" } else { "do" },
+                    others.len(), if others.len() == 1 { "" } else { "es" }, examples.join(", "));
+            }
+        } else {
+            println!("No guess is sure to win next turn.");
+        }
+    } else if options.json {
+        let guess = or_exit(solver.best_guess(&constraint_acc, false));
+        let remaining = solver.remaining_words(&constraint_acc);
+        let mut output = json!({
+            "best_guess": guess.as_str(),
+            "remaining": remaining.len()
+        });
+        if remaining.len() < CANDIDATE_LIST_THRESHOLD {
+            let candidates: Vec<&str> = remaining.iter().map(|w| w.as_str()).collect();
+            output["candidates"] = json!(candidates);
+        }
+        println!("{}", output);
+    } else {
+        let guess = or_exit(solver.best_guess(&constraint_acc, true));
 
-        println!("{:?}", result);
+        println!("Best guess: {}", guess.as_str());
     }
 }
 
 /// Return how many guesses it took to find the word.
 fn main()
 {
-    let cli = Cli::parse();
-    let mut cache_path = dirs::cache_dir().unwrap();
-    cache_path.push("wordle-solve.cache");
-    let cache_string = fs::read_to_string(&cache_path).unwrap_or_default();
-    let mut cache : HashMap<String, usize> = serde_json::from_str(cache_string.as_str()).unwrap_or_default();
-
-    let (words, hash) = read_words(&cli.words.unwrap_or("words".to_string())).unwrap();
-    let word_length = words.first().unwrap().len();
-    let solver = WordleSolver {
-        words,
-        first_guess: Mutex::new(cache.get(&hash).copied())
+    let Cli { command, solve: solve_args, mut options } = Cli::parse();
+    // The narration would end up in the middle of the JSON on stdout.
+    if options.json {
+        options.verbose = 0;
+    }
+    #[cfg(feature = "log")]
+    env_logger::init();
+    let command = match command {
+        // Clap can't tell that these belong to the default command.
+        Some(_) if solve_args.is_given() => {
+            Cli::command()
+                    .error(ErrorKind::ArgumentConflict, "Rows and their options only go with the solve command.")
+                    .exit();
+        },
+        Some(command) => command,
+        None => Command::Solve(solve_args)
     };
+    // Feedback doesn't need a word list.
+    if let Command::Feedback { guess, answer } = &command {
+        let (guess, answer) = (Word::new(guess.clone()), Word::new(answer.clone()));
+        if guess.len() != answer.len() {
+            eprintln!("'{}' has {} letters, but '{}' has {}.", guess.as_str(), guess.len(), answer.as_str(),
+                answer.len());
+            process::exit(1);
+        }
+        let pattern = feedback_pattern(&guess, &answer);
+        if options.json {
+            println!("{}", json!({"pattern": pattern}));
+        } else {
+            println!("{}", pattern);
+        }
+        return;
+    }
+    if let Some(threads) = options.threads.filter(|threads| *threads > 0) {
+        or_exit(rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global()
+                .map_err(|error| format!("Failed to start {} threads: {}", threads, error)));
+    }
+    let cache_path = match &options.cache_path {
+        _ if options.no_cache => None,
+        Some(path) => Some(PathBuf::from(path)),
+        None => default_cache_path()
+    };
+    let cache_string = cache_path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default();
+    let mut cache : HashMap<String, DecisionTree> = serde_json::from_str(cache_string.as_str()).unwrap_or_default();
 
-    if cli.test.is_some() {
-        let answer = Word::new(cli.test.unwrap());
-        solver.test(&answer, true);
-
-    } else if cli.full_test {
-        solver.full_test();
+    let builtin = options.words.is_none() && !Path::new(DEFAULT_WORDS).exists();
+    let words_path = match &options.words {
+        _ if builtin => "the built-in word list".to_string(),
+        Some(path) => path.clone(),
+        None => DEFAULT_WORDS.to_string()
+    };
+    // A word list from stdin might be different next time, so don't cache
+    // anything for it.
+    let mut use_cache = cache_path.is_some() && words_path != "-";
+    if let Command::CheckDictionary = command {
+        let check = or_exit(if builtin { check_words_from_str(BUILTIN_WORDS) } else { check_words(&words_path) });
+        if options.json {
+            println!("{}", json!({
+                "words": check.words,
+                "lengths": check.lengths,
+                "duplicates": check.duplicates,
+                "non_alphabetic": check.non_alphabetic,
+                "ok": check.is_ok()
+            }));
+        } else {
+            println!("{}", check);
+        }
+        if !check.is_ok() {
+            process::exit(1);
+        }
         return;
+    }
+    let WordList { words, mut hash, .. } = if builtin {
+        if !options.quiet {
+            eprintln!("Note: There's no {} file here, so the built-in word list is used.", DEFAULT_WORDS);
+        }
+        warn_about_words(or_exit(read_words_from_str(BUILTIN_WORDS, options.word_length)), &words_path,
+            options.word_length)
     } else {
-        let mut constraint_acc = Constraint::new(word_length);
-        for constraint_string in cli.constraint {
-            let constraint = Constraint::from_string(&constraint_string, word_length);
-            constraint_acc.update(&constraint);
+        read_words_or_exit(&words_path, options.word_length)
+    };
+    if let Command::DictHash = command {
+        println!("{}", hash);
+        return;
+    }
+    let word_length = match words.first() {
+        Some(word) => word.len(),
+        None => {
+            eprintln!("The dictionary is empty: there are no words in {}.", words_path);
+            process::exit(1);
         }
+    };
+    let guesses = match &options.guesses {
+        Some(path) => {
+            use_cache &= path != "-";
+            let WordList { words: guesses, hash: guesses_hash, .. } = read_words_or_exit(path, options.word_length);
+            if guesses.is_empty() {
+                eprintln!("The guess list is empty: there are no words in {}.", path);
+                process::exit(1);
+            }
+            if guesses.first().unwrap().len() != word_length {
+                eprintln!("Words in {} are not the same length as the answers.", path);
+                process::exit(1);
+            }
+            // The best guesses depend on the guess list too.
+            hash = format!("{}:{}", hash, guesses_hash);
+            Some(guesses)
+        },
+        None => None
+    };
+    let words: Vec<Word> = match &options.weights {
+        Some(path) => {
+            let WeightList { weights, hash: weights_hash } = or_exit(read_weights(path));
+            // And on how likely each answer is.
+            hash = format!("{}:{}", hash, weights_hash);
+            let words: Vec<Word> = words.into_iter()
+                    .map(|word| {
+                        let weight = weights.get(word.as_str()).copied().unwrap_or(1.0);
+                        word.with_weight(weight)
+                    })
+                    .collect();
+            if words.iter().all(|word| word.weight() == 0.0) {
+                eprintln!("Every word in {} has a weight of 0 in {}, so none can be the answer.",
+                    words_path, path);
+                process::exit(1);
+            }
+            words
+        },
+        None => words
+    };
+    if options.opening_pair {
+        // Opening pairs play differently, so cache them separately.
+        hash = format!("{}:pair", hash);
+    }
+    let hash = format!("{}:{}:{}:{}", CACHE_VERSION, strategy(&options), word_length, hash);
+    // Entries from older versions are keyed differently and never match.
+    cache.retain(|key, _| key.starts_with(&format!("{}:", CACHE_VERSION)));
+    let solver = WordleSolver::new(words, guesses, None, options.scoring)
+            .with_hard_mode(options.hard)
+            .with_feedback_matrix(options.feedback_matrix)
+            .with_seed(options.seed)
+            .with_tiebreak(options.tiebreak)
+            .with_depth(options.depth)
+            .with_endgame_threshold(options.endgame_threshold)
+            .with_exact_below(options.exact_below)
+            .with_exclude_guessed(options.exclude_guessed)
+            .with_max_guesses(options.max_guesses)
+            .with_opening_pair(options.opening_pair)
+            .with_quiet(options.quiet)
+            .with_timing(options.time)
+            .with_strict(options.strict)
+            .with_opener_strategy(options.opener_strategy.clone())
+            .with_verbosity(if options.quiet { Verbosity::Quiet } else { Verbosity::from(options.verbose) })
+            // best-opener starts from scratch, so it mustn't see the cached tree.
+            .with_decision_tree(if use_cache && !matches!(command, Command::BestOpener) {
+                let tree = cache.remove(&hash);
+                #[cfg(feature = "log")]
+                match &tree {
+                    Some(tree) => log::info!("Cache hit for {}: {} guesses", hash, tree.node_count()),
+                    None => log::info!("Cache miss for {}", hash)
+                }
+                tree
+            } else {
+                None
+            });
+    if let OpenerStrategy::Word(word) = &options.opener_strategy {
+        if !solver.guesses().iter().any(|guess| guess.as_str() == word) {
+            eprintln!("The opener '{}' isn't in the word list.", word);
+            process::exit(1);
+        }
+    }
 
-        let guess = solver.best_guess(&constraint_acc, true).unwrap();
-
-        println!("Best guess: {}", guess.word);
+    match command {
+        Command::Precompute => {
+            if !use_cache {
+                eprintln!("Error: precompute saves the decision tree in the cache, but nothing is cached in this run.");
+                process::exit(1);
+            }
+            let tree = or_exit(solver.build_decision_tree());
+            println!("Precomputed {} guesses.", tree.node_count());
+            cache.insert(hash.clone(), tree);
+        },
+        Command::DictHash | Command::CheckDictionary | Command::Feedback { .. } => unreachable!(),
+        Command::BestOpener => {
+            let (guess, score) = or_exit(solver.best_opener());
+            if options.json {
+                println!("{}", json!({"best_opener": guess.as_str(), "score": score}));
+            } else {
+                println!("Best opener: {}  {}", guess.as_str(), score);
+            }
+            // Keep a cached tree that starts with the same guess, since it
+            // knows more than the first guess.
+            if cache.get(&hash).is_some_and(|tree| tree.guess != guess.as_str()) {
+                cache.remove(&hash);
+            }
+        },
+        Command::OpenerStats => {
+            let stats = or_exit(solver.opener_stats());
+            if options.json {
+                println!("{}", json!({
+                    "opener": stats.opener.as_str(),
+                    "mean": stats.mean,
+                    "max": stats.max,
+                    "buckets": stats.buckets.iter().collect::<Vec<_>>()
+                }));
+            } else {
+                println!("Opener:  {}", stats.opener.as_str());
+                println!("Mean:    {:.3} words left", stats.mean);
+                println!("Max:     {} words left", stats.max);
+                println!("Left  Patterns");
+                for (left, patterns) in &stats.buckets {
+                    println!("{:>4}  {}", left, patterns);
+                }
+            }
+        },
+        Command::WorstOpeners { n } => {
+            print_scores(&or_exit(solver.worst_guesses(&Constraint::new(word_length), n)), options.json);
+        },
+        Command::OpenerScores { path } => {
+            let scores = or_exit(solver.ranked_guesses(&Constraint::new(word_length), usize::MAX));
+            or_exit(write_scores(&path, &scores));
+        },
+        Command::Test { word } => {
+            let answer = Word::new(word);
+            let result = solver.test(&answer, !options.json);
+            if options.json {
+                let guesses: Vec<&str> = result.guesses().iter().map(|g| g.as_str()).collect();
+                println!("{}", json!(guesses));
+            }
+            if !result.is_solved() {
+                eprintln!("Gave up after {} guesses.", result.guesses().len());
+            }
+        },
+        Command::Verify { path } => {
+            let path: Vec<&str> = path.iter().map(String::as_str).collect();
+            let result = solver.verify(&path, !options.json);
+            if options.json {
+                match &result {
+                    Ok(guesses) => println!("{}", json!({"valid": true, "guesses": guesses.len()})),
+                    Err(error) => println!("{}", json!({"valid": false, "error": error}))
+                }
+            } else {
+                match &result {
+                    Ok(guesses) => println!("Valid: solved in {} guesses.", guesses.len()),
+                    Err(error) => println!("Invalid: {}", error)
+                }
+            }
+            if result.is_err() {
+                process::exit(1);
+            }
+        },
+        Command::Anti { word } => {
+            let answer = Word::new(word);
+            let guesses = solver.anti(&answer, !options.json);
+            if options.json {
+                let guesses: Vec<&str> = guesses.iter().map(|g| g.as_str()).collect();
+                println!("{}", json!(guesses));
+            } else {
+                println!("Lasted {} guesses.", guesses.len());
+            }
+        },
+        Command::Simulate { answer_seed } => {
+            let mut rng = match answer_seed.or(options.seed) {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy()
+            };
+            let answer = &solver.answers()[rng.gen_range(0..solver.answers().len())];
+            let result = solver.test(answer, !options.json);
+            let guesses: Vec<&str> = result.guesses().iter().map(|g| g.as_str()).collect();
+            if options.json {
+                println!("{}", json!({"answer": answer.as_str(), "guesses": guesses}));
+            } else if result.is_solved() {
+                println!("Solved in {} guesses.", guesses.len());
+            } else {
+                println!("Gave up after {} guesses. The answer was {}.", guesses.len(), answer.as_str());
+            }
+        },
+        Command::Absurdle => {
+            let guesses = or_exit(solver.absurdle(!options.json));
+            if options.json {
+                let guesses: Vec<&str> = guesses.iter().map(|g| g.as_str()).collect();
+                println!("{}", json!(guesses));
+            } else {
+                println!("Solved in {} guesses.", guesses.len());
+            }
+        },
+        Command::Dordle { boards: streams } => {
+            let mut boards = Vec::new();
+            for stream in streams {
+                let mut board = Constraint::new(word_length);
+                for row in stream.split(',').filter(|row| !row.is_empty()) {
+                    or_exit(solver.merge(&mut board, &or_exit(Constraint::from_string(row, word_length))));
+                }
+                boards.push(board);
+            }
+            let guess = or_exit(solver.best_dordle_guess(&boards));
+            println!("Best guess: {}", guess.as_str());
+        },
+        Command::FullTest { csv, answers_file, digest, #[cfg(feature = "plot")] plot } => {
+            let answers = answers_file.map(|path| read_words_or_exit(&path, Some(word_length)).words);
+            let answers = answers.as_deref().unwrap_or(solver.answers());
+            // With --json, each game is printed as a line of JSON as soon as
+            // it's over.
+            let print_game = |answer: &Word, result: &GameResult| {
+                if options.json {
+                    let sequence: Vec<&str> = result.guesses().iter().map(|guess| guess.as_str()).collect();
+                    let line = json!({
+                        "answer": answer.as_str(),
+                        "solved": result.is_solved(),
+                        "guesses": sequence.len(),
+                        "sequence": sequence
+                    });
+                    // Stop at once if nobody's reading any more, e.g. after
+                    // `| head`, instead of failing for every game left.
+                    if let Err(error) = writeln!(io::stdout().lock(), "{}", line) {
+                        eprintln!("Failed to write to stdout: {}", error);
+                        process::exit(1);
+                    }
+                }
+            };
+            let mut file = csv.map(|path| BufWriter::new(or_exit(File::create(&path)
+                    .map_err(|error| format!("Failed to create {}: {}", path, error)))));
+            let summary = or_exit(solver.test_answers_with(answers, options.verbose > 0,
+                file.as_mut().map(|file| file as &mut (dyn Write + Send)), &print_game));
+            #[cfg(feature = "plot")]
+            if let Some(path) = plot {
+                or_exit(wordle_solve::plot_histogram(&summary, &path));
+            }
+            if digest {
+                println!("{}", summary.digest());
+            } else if !options.json {
+                println!("{}", summary);
+            }
+            return;
+        },
+        Command::CompareScorers => {
+            compare_scorers(solver, &options);
+            return;
+        },
+        Command::BenchScoring { seconds } => {
+            let duration = or_exit(Duration::try_from_secs_f64(seconds)
+                    .map_err(|_| format!("--seconds must be a number of seconds, not {}.", seconds)));
+            let (scored, elapsed) = solver.bench_scoring(duration);
+            let rate = scored as f64 / elapsed.as_secs_f64();
+            let threads = rayon::current_num_threads();
+            if options.json {
+                println!("{}", json!({"guesses_per_second": rate, "threads": threads}));
+            } else {
+                println!("{:.1} guesses/s on {} thread{}", rate, threads, if threads == 1 { "" } else { "s" });
+            }
+            return;
+        },
+        Command::Interactive(rows) => {
+            let constraint = read_rows(&solver, &rows, word_length);
+            check_contradiction(&constraint);
+            interactive(&solver, constraint, &rows, word_length, options.time);
+        },
+        Command::Solve(args) => {
+            let started = Instant::now();
+            solve(&solver, &args, &options, word_length);
+            if options.time {
+                io::stdout().flush().unwrap();
+                eprintln!("Took {:.3}s.", started.elapsed().as_secs_f64());
+            }
+        },
+        #[cfg(feature = "server")]
+        Command::Serve { port, bind } => or_exit(wordle_solve::serve(&solver, &bind, port))
     }
 
-    let first_guess = *solver.first_guess.lock().unwrap();
-    if first_guess.is_some() {
-        cache.insert(hash, first_guess.unwrap());
+    let cache_path = match cache_path {
+        Some(cache_path) if use_cache => cache_path,
+        _ => return
+    };
+    let tree = match solver.decision_tree() {
+        // A tree with only the first guess in it doesn't have the opening
+        // pair's second guess yet.
+        Some(tree) if !options.opening_pair || !tree.next.is_empty() => Some(tree.clone()),
+        _ if options.opening_pair => solver.opening_tree(),
+        _ => solver.first_guess()
+                .map(|first_guess| DecisionTree::new(solver.guesses()[first_guess].as_str().to_string()))
+    };
+    if let Some(tree) = tree {
+        cache.entry(hash).or_insert(tree);
     }
     let cache_data = serde_json::to_string(&cache).unwrap();
-    fs::write(&cache_path, &cache_data).unwrap();
+    // The answer has been printed already, so a cache that can't be saved
+    // only makes the next run slower.
+    match fs::write(&cache_path, &cache_data) {
+        Ok(()) => {
+            #[cfg(feature = "log")]
+            log::info!("Wrote {} cache entries to {}", cache.len(), cache_path.display());
+        },
+        Err(error) => eprintln!("Warning: Failed to write the cache to {}: {}", cache_path.display(), error)
+    }
 }
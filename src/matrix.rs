@@ -0,0 +1,51 @@
+use crate::constraint::{feedback_code, MAX_CODE_LENGTH};
+use crate::scoring::Bucket;
+use crate::word::Word;
+use crate::parallel::*;
+
+/// The feedback code of every guess against every answer, so scoring can
+/// look patterns up instead of working them out again for every guess.
+pub(crate) struct FeedbackMatrix {
+    answer_count: usize,
+    pattern_count: usize,
+    codes: Vec<u16>,
+    weights: Vec<f64>
+}
+
+impl FeedbackMatrix {
+    /// Returns `None` if the words are too long to encode.
+    pub(crate) fn new(guesses: &[Word], answers: &[Word]) -> Option<Self>
+    {
+        let word_length = answers.first()?.chars().count();
+        if word_length > MAX_CODE_LENGTH {
+            return None;
+        }
+        let codes = guesses
+                .par_iter()
+                .flat_map_iter(|guess| answers.iter().map(|answer| feedback_code(guess, answer)))
+                .collect();
+        Some(Self {
+            answer_count: answers.len(),
+            pattern_count: 3usize.pow(word_length as u32),
+            codes,
+            weights: answers.iter().map(|answer| answer.weight()).collect()
+        })
+    }
+
+    /// Group the given answers by their feedback for the guess at index
+    /// `guess`, returning only the non-empty buckets.
+    pub(crate) fn buckets(&self, guess: usize, answers: &[usize]) -> Vec<Bucket>
+    {
+        let row = &self.codes[guess * self.answer_count..(guess + 1) * self.answer_count];
+        let mut buckets = vec![Bucket::default(); self.pattern_count];
+        for answer in answers {
+            let bucket = &mut buckets[row[*answer] as usize];
+            bucket.size += 1;
+            bucket.weight += self.weights[*answer];
+        }
+        // The last code is the one that's all green.
+        buckets[self.pattern_count - 1].solved = true;
+        buckets.retain(|b| b.size > 0);
+        buckets
+    }
+}
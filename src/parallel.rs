@@ -0,0 +1,62 @@
+//! Rayon's parallel iterators with the `parallel` feature, and stand-ins
+//! that run on the current thread without it, for targets like WebAssembly
+//! that have no threads. Only what the crate uses is covered.
+
+#[cfg(feature = "parallel")]
+pub(crate) use indicatif::ParallelProgressIterator;
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::current_num_threads;
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::prelude::*;
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) use indicatif::ProgressIterator as ParallelProgressIterator;
+#[cfg(not(feature = "parallel"))]
+pub(crate) use sequential::*;
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    use std::iter::FlatMap;
+    use std::slice::Iter;
+
+    pub(crate) fn current_num_threads() -> usize
+    {
+        1
+    }
+
+    /// `par_iter` on a slice is `iter`.
+    pub(crate) trait IntoParallelRefIterator<'a> {
+        type Item: 'a;
+
+        fn par_iter(&'a self) -> Iter<'a, Self::Item>;
+    }
+
+    impl<'a, T: 'a> IntoParallelRefIterator<'a> for [T] {
+        type Item = T;
+
+        fn par_iter(&'a self) -> Iter<'a, T>
+        {
+            self.iter()
+        }
+    }
+
+    /// `into_par_iter` is `into_iter`.
+    pub(crate) trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter
+        {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    /// The methods rayon has that iterators don't.
+    pub(crate) trait ParallelIterator: Iterator + Sized {
+        fn flat_map_iter<U: IntoIterator, F: FnMut(Self::Item) -> U>(self, f: F) -> FlatMap<Self, U, F>
+        {
+            self.flat_map(f)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+}
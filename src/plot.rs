@@ -0,0 +1,32 @@
+use crate::summary::Summary;
+use plotters::prelude::*;
+
+/// Draw how many answers took each number of guesses as a bar chart, and
+/// save it as an SVG file at `path`.
+pub fn plot_histogram(summary: &Summary, path: &str) -> Result<(), String>
+{
+    let histogram = summary.histogram();
+    let max_count = histogram.iter().copied().max().unwrap_or(0);
+    let root = SVGBackend::new(path, (640, 480)).into_drawing_area();
+    let error = |error| format!("Failed to plot {}: {}", path, error);
+    root.fill(&WHITE).map_err(error)?;
+    let mut chart = ChartBuilder::on(&root)
+            .caption(format!("{} words, mean {:.3} guesses", summary.len(), summary.mean()), ("sans-serif", 24))
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d((1..histogram.len() as u32).into_segmented(), 0..max_count + max_count / 10 + 1)
+            .map_err(error)?;
+    chart.configure_mesh()
+            .disable_x_mesh()
+            .x_desc("Guesses")
+            .y_desc("Words")
+            .draw()
+            .map_err(error)?;
+    chart.draw_series(Histogram::vertical(&chart)
+            .style(BLUE.filled())
+            .margin(10)
+            .data(histogram.iter().enumerate().map(|(i, &count)| (i as u32 + 1, count))))
+            .map_err(error)?;
+    root.present().map_err(error)
+}
@@ -0,0 +1,178 @@
+use clap::ValueEnum;
+use crate::constraint::{feedback, wordle_guess, Constraint, Feedback};
+use crate::word::Word;
+use std::collections::HashMap;
+
+/// The answers that get the same feedback pattern for a guess.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bucket {
+    /// How many answers there are.
+    pub size: usize,
+    /// The sum of their weights.
+    pub weight: f64,
+    /// Whether the pattern is all green, so the game is already won.
+    pub solved: bool
+}
+
+impl Bucket {
+    pub(crate) fn add(&mut self, answer: &Word)
+    {
+        self.size += 1;
+        self.weight += answer.weight();
+    }
+}
+
+/// How `WordleSolver` ranks guesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Scoring {
+    /// Maximize the number of words each possible answer eliminates.
+    Eliminations,
+    /// Maximize the expected information gained, in bits.
+    Entropy,
+    /// Minimize the number of words left in the worst case.
+    Minimax,
+    /// Minimize the expected number of guesses still needed afterwards.
+    Expected
+}
+
+impl Scoring {
+    /// Score `guess` against the remaining `words`. Higher is better.
+    pub fn score(&self, guess: &Word, words: &Vec<&Word>, constraint: &Constraint) -> f64
+    {
+        match self {
+            Scoring::Eliminations => score_guess_count_eliminations(guess, words, constraint),
+            Scoring::Entropy => score_guess_entropy(guess, words, constraint),
+            Scoring::Minimax => -(score_guess_minimax(guess, words, constraint) as f64),
+            Scoring::Expected => -score_guess_expected(guess, words, constraint)
+        }
+    }
+
+    /// Score a guess from the groups its feedback patterns split the
+    /// remaining words into. Gives the same result as `score`.
+    pub fn score_buckets(&self, buckets: &[Bucket]) -> f64
+    {
+        match self {
+            Scoring::Eliminations => {
+                // Every answer leaves exactly the words in its own bucket.
+                let total: f64 = buckets.iter().map(|b| b.weight).sum();
+                total * total - buckets.iter().map(|b| b.weight * b.weight).sum::<f64>()
+            },
+            Scoring::Entropy => entropy(buckets),
+            Scoring::Minimax => -(buckets.iter().map(|b| b.size).max().unwrap_or(0) as f64),
+            Scoring::Expected => -expected_guesses(buckets)
+        }
+    }
+
+    /// Bonus added to the score of a guess that might be the answer itself.
+    /// Scorers without a bonus still prefer such guesses when scores tie.
+    pub fn candidate_bonus(&self) -> f64
+    {
+        match self {
+            Scoring::Eliminations => 1.0,
+            Scoring::Entropy | Scoring::Minimax | Scoring::Expected => 0.0
+        }
+    }
+}
+
+/// Count the words each possible answer would eliminate, weighted by how
+/// likely that answer is. The eliminated words are weighted too, so with the
+/// default weights of 1 this is a plain count.
+pub fn score_guess_count_eliminations(guess: &Word, words: &Vec<&Word>, constraint: &Constraint) -> f64
+{
+    score_guess_count_eliminations_above(guess, words, constraint, f64::NEG_INFINITY).unwrap()
+}
+
+/// Like `score_guess_count_eliminations`, but give up and return `None` as
+/// soon as it's clear the score will be below `bound`. The score only goes
+/// down as each answer is counted, so this can stop early.
+pub(crate) fn score_guess_count_eliminations_above(guess: &Word, words: &Vec<&Word>,
+        constraint: &Constraint, bound: f64) -> Option<f64>
+{
+    let total: f64 = words.iter().map(|w| w.weight()).sum();
+    let mut score = total * total;
+    for answer in words {
+        // If the word is `word`, then how good is this guess?
+        let mut answer_constraint = wordle_guess(guess, answer);
+        answer_constraint.update(constraint);
+        let compact = answer_constraint.compact();
+        let left: f64 = words.iter()
+                .filter(|w| match &compact {
+                    Some(compact) if w.ascii().is_some() => compact.allows_fast(w),
+                    _ => answer_constraint.allows(w)
+                })
+                .map(|w| w.weight())
+                .sum();
+        score -= answer.weight() * left;
+        if score < bound {
+            return None;
+        }
+    }
+    Some(score)
+}
+
+/// Group `words` by the feedback pattern they get for `guess`.
+pub(crate) fn feedback_buckets(guess: &Word, words: &Vec<&Word>) -> HashMap<Vec<Feedback>, Bucket>
+{
+    let mut buckets: HashMap<Vec<Feedback>, Bucket> = HashMap::new();
+    for answer in words {
+        buckets.entry(feedback(guess, answer)).or_default().add(answer);
+    }
+    for (pattern, bucket) in buckets.iter_mut() {
+        bucket.solved = pattern.iter().all(|f| *f == Feedback::Green);
+    }
+    buckets
+}
+
+/// Score a guess by the Shannon entropy of the feedback patterns it produces
+/// over `words`, i.e. how many bits of information it is expected to reveal.
+pub fn score_guess_entropy(guess: &Word, words: &Vec<&Word>, _constraint: &Constraint) -> f64
+{
+    let buckets: Vec<Bucket> = feedback_buckets(guess, words).into_values().collect();
+    entropy(&buckets)
+}
+
+/// Shannon entropy, in bits, of the distribution given by the bucket weights.
+fn entropy(buckets: &[Bucket]) -> f64
+{
+    let total: f64 = buckets.iter().map(|b| b.weight).sum();
+    -buckets.iter()
+            .filter(|b| b.weight > 0.0)
+            .map(|b| {
+                let p = b.weight / total;
+                p * p.log2()
+            })
+            .sum::<f64>()
+}
+
+/// Return the size of the largest group of `words` that share a feedback
+/// pattern for `guess`. This is how many words are left in the worst case.
+pub fn score_guess_minimax(guess: &Word, words: &Vec<&Word>, _constraint: &Constraint) -> usize
+{
+    feedback_buckets(guess, words).into_values().map(|b| b.size).max().unwrap_or(0)
+}
+
+/// Estimate how many more guesses it takes to find the answer after guessing
+/// `guess`, averaged over `words`. Lower is better.
+pub fn score_guess_expected(guess: &Word, words: &Vec<&Word>, _constraint: &Constraint) -> f64
+{
+    let buckets: Vec<Bucket> = feedback_buckets(guess, words).into_values().collect();
+    expected_guesses(&buckets)
+}
+
+/// The expected number of guesses still needed, given the groups a guess
+/// splits the words into. Each group is weighted by how likely the answer is
+/// to be in it. The all-green group takes no more guesses, and any other
+/// group of one word takes one more. For bigger groups this assumes every
+/// further guess cuts the group down to a quarter, so a group of n words takes
+/// 1 + log4(n) guesses; that's exact for two words, where half the time the
+/// first guess is right.
+fn expected_guesses(buckets: &[Bucket]) -> f64
+{
+    let total: f64 = buckets.iter().map(|b| b.weight).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    buckets.iter()
+            .map(|b| if b.solved { 0.0 } else { b.weight / total * (1.0 + (b.size as f64).log2() / 2.0) })
+            .sum()
+}
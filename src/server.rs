@@ -0,0 +1,59 @@
+use crate::constraint::Constraint;
+use crate::logging::info;
+use crate::solver::WordleSolver;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Serve `solver` over HTTP on `port` of the address `bind`, e.g.
+/// `127.0.0.1` for this machine only, until the process is stopped. Each
+/// POST takes a JSON list of result rows, e.g. `["-r -a ~i -s -e"]`, and
+/// gets back the best guess and how many words are left. The same solver
+/// answers every request, so the first guess is only ever scored once.
+pub fn serve(solver: &WordleSolver, bind: &str, port: u16) -> Result<(), String>
+{
+    let server = Server::http((bind, port))
+            .map_err(|error| format!("Failed to listen on {} port {}: {}", bind, port, error))?;
+    for request in server.incoming_requests() {
+        respond(solver, request);
+    }
+    Ok(())
+}
+
+fn respond(solver: &WordleSolver, mut request: Request)
+{
+    let (status, body) = if *request.method() != Method::Post {
+        (405, json!({"error": "Only POST is supported."}))
+    } else {
+        let mut body = String::new();
+        let result = request.as_reader().read_to_string(&mut body)
+                .map_err(|error| format!("Failed to read the request: {}", error))
+                .and_then(|_| best_guess(solver, &body));
+        match result {
+            Ok(value) => (200, value),
+            Err(error) => (400, json!({"error": error}))
+        }
+    };
+    info!("{} {} from {}: {} {}", request.method(), request.url(),
+        request.remote_addr().map_or("unknown".to_string(), |addr| addr.to_string()), status, body);
+    let mut response = Response::from_string(body.to_string()).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response = response.with_header(header);
+    }
+    // The client may have hung up already; there's nobody to tell.
+    let _ = request.respond(response);
+}
+
+/// Parse the rows in `body` and find the best guess for them.
+fn best_guess(solver: &WordleSolver, body: &str) -> Result<Value, String>
+{
+    let rows: Vec<String> = serde_json::from_str(body)
+            .map_err(|error| format!("The request should be a JSON list of rows: {}", error))?;
+    let word_length = solver.answers().first().ok_or("The word list is empty.")?.len();
+    let mut constraint = Constraint::new(word_length);
+    for row in &rows {
+        solver.merge(&mut constraint, &Constraint::from_string(row, word_length)?)?;
+    }
+    let guess = solver.best_guess(&constraint, false)?;
+    let remaining = solver.remaining_count(&constraint);
+    Ok(json!({"best_guess": guess.as_str(), "remaining": remaining}))
+}
@@ -0,0 +1,1409 @@
+use clap::ValueEnum;
+use crate::constraint::{
+    feedback, feedback_pattern, filter_indices, filter_words, wordle_guess, Constraint, Feedback
+};
+use crate::game::Game;
+use crate::logging::{debug, info};
+use crate::lru::Lru;
+use crate::matrix::FeedbackMatrix;
+use crate::parallel::*;
+use crate::scoring::{score_guess_count_eliminations, score_guess_count_eliminations_above, Bucket, Scoring};
+use crate::summary::{Summary, MAX_GUESSES};
+use crate::tree::DecisionTree;
+use crate::word::{char_frequency, position_frequency, Word};
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::hint;
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(not(feature = "wasm"))]
+use std::time::Instant;
+// std's Instant panics in the browser.
+#[cfg(feature = "wasm")]
+use web_time::Instant;
+
+/// Remaining candidates are listed when there are fewer than this many.
+pub const CANDIDATE_LIST_THRESHOLD: usize = 15;
+
+/// `test` gives up after this many guesses, unless told otherwise.
+pub const DEFAULT_MAX_GUESSES: usize = 20;
+
+/// Looking more than one guess ahead is only tried when at most this many
+/// words are left.
+const LOOKAHEAD_WORDS: usize = 30;
+
+/// How many of the best scoring guesses looking ahead considers at each
+/// step.
+const LOOKAHEAD_BREADTH: usize = 8;
+
+/// How many constraints `remaining_words` remembers the answers for.
+const REMAINING_CACHE_SIZE: usize = 16;
+
+/// How `WordleSolver` picks between guesses with the same score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Tiebreak {
+    /// The alphabetically last word.
+    Alpha,
+    /// The word with the most different letters nothing is known about yet.
+    Coverage,
+    /// A random word, from the solver's seed if it has one.
+    Random
+}
+
+/// How `WordleSolver` picks the first guess.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum OpenerStrategy {
+    /// The best scoring guess, like every other guess.
+    #[default]
+    Score,
+    /// The guess with the most different vowels, and then the most different
+    /// consonants, whatever it scores.
+    Vowels,
+    /// Always this word.
+    Word(String)
+}
+
+impl FromStr for OpenerStrategy {
+    type Err = String;
+
+    /// Parse `score`, `vowels` or `word:` followed by the word.
+    fn from_str(string: &str) -> Result<Self, String>
+    {
+        match string {
+            "score" => Ok(OpenerStrategy::Score),
+            "vowels" => Ok(OpenerStrategy::Vowels),
+            _ => match string.strip_prefix("word:") {
+                Some(word) if !word.is_empty() => Ok(OpenerStrategy::Word(word.to_string())),
+                _ => Err(format!("'{}' should be score, vowels or word: followed by a word, e.g. word:crane.",
+                    string))
+            }
+        }
+    }
+}
+
+impl fmt::Display for OpenerStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            OpenerStrategy::Score => write!(f, "score"),
+            OpenerStrategy::Vowels => write!(f, "vowels"),
+            OpenerStrategy::Word(word) => write!(f, "word:{}", word)
+        }
+    }
+}
+
+/// How much `WordleSolver` says about the guesses it picks. Each level says
+/// everything the ones before it do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Nothing but the guesses themselves.
+    #[default]
+    Quiet,
+    /// How many words are left, and how many each guess ruled out.
+    Status,
+    /// The words that are left, when there are only a few.
+    Candidates,
+    /// Every word that's left, with its score as a guess.
+    Scores
+}
+
+impl From<u8> for Verbosity {
+    /// 0 is `Quiet`, and 3 or more is `Scores`, like counting `-v` flags.
+    fn from(level: u8) -> Self
+    {
+        match level {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Status,
+            2 => Verbosity::Candidates,
+            _ => Verbosity::Scores
+        }
+    }
+}
+
+/// How a game played by `WordleSolver::test` ended, with the guesses made.
+#[derive(Clone, PartialEq, Eq)]
+pub enum GameResult<'a> {
+    Solved(Vec<&'a Word>),
+    /// The answer wasn't found within the solver's maximum number of guesses.
+    Failed(Vec<&'a Word>)
+}
+
+impl<'a> GameResult<'a> {
+    pub fn guesses(&self) -> &Vec<&'a Word>
+    {
+        match self {
+            GameResult::Solved(guesses) | GameResult::Failed(guesses) => guesses
+        }
+    }
+
+    pub fn is_solved(&self) -> bool
+    {
+        matches!(self, GameResult::Solved(_))
+    }
+}
+
+/// How well the first guess narrows down the answers, from
+/// `WordleSolver::opener_stats`.
+pub struct OpenerStats<'a> {
+    pub opener: &'a Word,
+    /// How many answers are left after the opener, on average over every
+    /// answer.
+    pub mean: f64,
+    /// The most answers left after the opener.
+    pub max: usize,
+    /// For each number of answers left, how many feedback patterns leave
+    /// that many.
+    pub buckets: BTreeMap<usize, usize>
+}
+
+/// A bar counting up to `length`, with an estimate of the time left, or a
+/// hidden one unless `show` is set.
+fn progress_bar(length: usize, show: bool) -> ProgressBar
+{
+    if !show {
+        return ProgressBar::hidden();
+    }
+    ProgressBar::new(length as u64).with_style(ProgressStyle::with_template("{bar:60} {pos}/{len} {eta}").unwrap())
+}
+
+/// Whether to print in color: only on a terminal, and not if the NO_COLOR
+/// environment variable is set.
+fn use_color() -> bool
+{
+    env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && io::stdout().is_terminal()
+}
+
+/// Show `guess` the way wordle would when the answer is `answer`, with a
+/// green, yellow or gray background behind each letter.
+fn colored_guess(guess: &Word, answer: &Word) -> String
+{
+    feedback(guess, answer).iter()
+            .zip(guess.chars())
+            .map(|(f, c)| {
+                let background = match f {
+                    Feedback::Green => 42,
+                    Feedback::Yellow => 43,
+                    Feedback::Gray => 100
+                };
+                format!("\x1b[1;30;{}m {} \x1b[0m", background, c)
+            })
+            .collect()
+}
+
+/// Print a guess the way `test` shows it, in color when that makes sense.
+fn print_guess(guess: &Word, answer: &Word)
+{
+    if use_color() {
+        println!("Guess: {}", colored_guess(guess, answer));
+    } else {
+        println!("Guess: {}", guess.as_str());
+    }
+}
+
+/// How many of the words left have each of `guess`'s letters in the same
+/// position, added up. A letter that comes up again in the guess only counts
+/// the first time, since it's the first one that tells the most.
+fn letter_frequency_score(guess: &Word, frequency: &[HashMap<char, usize>]) -> f64
+{
+    let mut seen = HashSet::new();
+    guess.chars()
+            .zip(frequency)
+            .filter(|(c, _counts)| seen.insert(*c))
+            .map(|(c, counts)| counts.get(&c).copied().unwrap_or(0) as f64)
+            .sum()
+}
+
+/// Picks guesses from a word list. The word lists and first guess cache
+/// are only reachable through its methods.
+pub struct WordleSolver {
+    /// Words that might be the answer.
+    answers: Vec<Word>,
+    /// Words that may be guessed.
+    guesses: Vec<Word>,
+    first_guess: Mutex<Option<usize>>,
+    /// Always follow the first guess with the same second guess.
+    opening_pair: bool,
+    /// The index of that second guess, once it has been computed.
+    second_guess: Mutex<Option<usize>>,
+    scoring: Scoring,
+    /// Only guess words that satisfy everything revealed so far.
+    hard_mode: bool,
+    matrix: Option<FeedbackMatrix>,
+    tree: Option<DecisionTree>,
+    /// Seed for choosing between guesses with the same score.
+    seed: Option<u64>,
+    tiebreak: Option<Tiebreak>,
+    /// How many guesses ahead to look when few words are left.
+    depth: usize,
+    /// Once this many words or fewer are left, only guess words that might
+    /// be the answer.
+    endgame_threshold: usize,
+    /// Score guesses with a quick heuristic while at least this many words
+    /// are left.
+    exact_below: Option<usize>,
+    /// Never suggest a word that was already guessed in the same game.
+    exclude_guessed: bool,
+    /// How many guesses `test` makes before giving up.
+    max_guesses: usize,
+    /// Never show a progress bar.
+    quiet: bool,
+    /// Report how long each guess in `test` took to pick.
+    timing: bool,
+    /// Check every merge of rows against the whole word list.
+    strict: bool,
+    /// How much to say about the guesses being picked.
+    verbosity: Verbosity,
+    opener: OpenerStrategy,
+    /// How often each letter appears in the answers.
+    letter_frequency: HashMap<char, usize>,
+    /// The indices of the answers that satisfy recently seen constraints.
+    remaining_cache: Mutex<Lru<Constraint, Vec<usize>>>
+}
+
+impl WordleSolver {
+    /// When `guesses` is `None`, every answer is also a legal guess, and
+    /// nothing else is. `first_guess` is the index into the guess list of a
+    /// previously computed best first guess, if one is known. Answers with a
+    /// weight of 0 can't be the answer, so they're only kept as guesses.
+    pub fn new(mut answers: Vec<Word>, guesses: Option<Vec<Word>>, first_guess: Option<usize>,
+            scoring: Scoring) -> Self
+    {
+        let guesses = guesses.unwrap_or_else(|| answers.clone());
+        answers.retain(|answer| answer.weight() > 0.0);
+        let letter_frequency = char_frequency(answers.iter().flat_map(Word::chars));
+        Self {
+            answers,
+            guesses,
+            first_guess: Mutex::new(first_guess),
+            opening_pair: false,
+            second_guess: Mutex::new(None),
+            scoring,
+            hard_mode: false,
+            matrix: None,
+            tree: None,
+            seed: None,
+            tiebreak: None,
+            depth: 1,
+            endgame_threshold: 0,
+            exact_below: None,
+            exclude_guessed: false,
+            max_guesses: DEFAULT_MAX_GUESSES,
+            quiet: false,
+            timing: false,
+            strict: false,
+            verbosity: Verbosity::Quiet,
+            opener: OpenerStrategy::Score,
+            letter_frequency,
+            remaining_cache: Mutex::new(Lru::new(REMAINING_CACHE_SIZE))
+        }
+    }
+
+    /// Break ties between equally good guesses with a random number generator
+    /// seeded with `seed`, so the choice is reproducible but not always the
+    /// same word. Without a seed, the alphabetically last word wins.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self
+    {
+        self.seed = seed;
+        self
+    }
+
+    /// Break ties between equally good guesses this way, instead of the way
+    /// `with_seed` says.
+    pub fn with_tiebreak(mut self, tiebreak: Option<Tiebreak>) -> Self
+    {
+        self.tiebreak = tiebreak;
+        self
+    }
+
+    /// Follow a previously built decision tree instead of scoring guesses,
+    /// for as long as the game stays on it.
+    pub fn with_decision_tree(mut self, tree: Option<DecisionTree>) -> Self
+    {
+        self.tree = tree;
+        self
+    }
+
+    pub fn decision_tree(&self) -> Option<&DecisionTree>
+    {
+        self.tree.as_ref()
+    }
+
+    /// Precompute the feedback of every guess against every answer. This
+    /// makes scoring much faster, but takes two bytes per guess/answer pair.
+    /// Words longer than 10 letters don't fit, and are scored without it.
+    pub fn with_feedback_matrix(mut self, enabled: bool) -> Self
+    {
+        self.matrix = if enabled {
+            FeedbackMatrix::new(&self.guesses, &self.answers)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Never suggest a word that was already guessed in the same game, as
+    /// long as there's anything else left to guess.
+    pub fn with_exclude_guessed(mut self, exclude_guessed: bool) -> Self
+    {
+        self.exclude_guessed = exclude_guessed;
+        self
+    }
+
+    /// Give up on a game in `test`, `test_dordle` or `absurdle` after
+    /// `max_guesses` guesses.
+    pub fn with_max_guesses(mut self, max_guesses: usize) -> Self
+    {
+        self.max_guesses = max_guesses;
+        self
+    }
+
+    /// Always make the same second guess, whatever feedback the first one
+    /// got, picked to split up the answers best together with the first.
+    /// This saves scoring on the second turn at the cost of a few guesses.
+    pub fn with_opening_pair(mut self, opening_pair: bool) -> Self
+    {
+        self.opening_pair = opening_pair;
+        self
+    }
+
+    /// Don't show progress bars, e.g. when the output is being piped
+    /// somewhere.
+    pub fn with_quiet(mut self, quiet: bool) -> Self
+    {
+        self.quiet = quiet;
+        self
+    }
+
+    /// When `test` shows its guesses, also report on stderr how long each
+    /// took to pick, and the total.
+    pub fn with_timing(mut self, timing: bool) -> Self
+    {
+        self.timing = timing;
+        self
+    }
+
+    /// Check that merging rows with `merge` doesn't change which words they
+    /// allow, going through every guess word each time. It's slow, so it's
+    /// off by default.
+    pub fn with_strict(mut self, strict: bool) -> Self
+    {
+        self.strict = strict;
+        self
+    }
+
+    /// How much to say about the guesses being picked, when asked to be
+    /// verbose. From `Status` on, `test` also shows how many of the words
+    /// left each guess ruled out.
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self
+    {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// While `exact_below` or more words are left, score guesses by how
+    /// common their letters are in those positions among the words left,
+    /// instead of with the solver's scorer. It's much faster, but not as
+    /// good. `None`, the default, always uses the scorer.
+    pub fn with_exact_below(mut self, exact_below: Option<usize>) -> Self
+    {
+        self.exact_below = exact_below;
+        self
+    }
+
+    /// Pick the first guess this way. A `Word` that isn't in the word list
+    /// is an error when the first guess is asked for.
+    pub fn with_opener_strategy(mut self, opener: OpenerStrategy) -> Self
+    {
+        self.opener = opener;
+        self
+    }
+
+    /// When only a few words are left, pick the guess that needs the fewest
+    /// guesses on average, trying the best few guesses `depth` guesses deep
+    /// instead of only scoring the next one. A depth of 1, the default,
+    /// only scores the next guess.
+    pub fn with_depth(mut self, depth: usize) -> Self
+    {
+        self.depth = depth;
+        self
+    }
+
+    /// Score guesses a different way. Everything worked out with the old
+    /// scorer is forgotten, but a feedback matrix is kept.
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self
+    {
+        self.scoring = scoring;
+        self.first_guess = Mutex::new(None);
+        self.second_guess = Mutex::new(None);
+        self.tree = None;
+        self
+    }
+
+    /// Once `threshold` words or fewer are left, only guess words that
+    /// might be the answer, the way hard mode always does. 0 never does.
+    pub fn with_endgame_threshold(mut self, threshold: usize) -> Self
+    {
+        self.endgame_threshold = threshold;
+        self
+    }
+
+    /// In hard mode every guess must itself satisfy the constraint.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self
+    {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    pub fn answers(&self) -> &Vec<Word>
+    {
+        &self.answers
+    }
+
+    pub fn guesses(&self) -> &Vec<Word>
+    {
+        &self.guesses
+    }
+
+    /// How long the words are, or 0 if there aren't any.
+    fn word_length(&self) -> usize
+    {
+        self.answers.first().map_or(0, Word::len)
+    }
+
+    /// Add what `row` says about the answer to `constraint`. With
+    /// `with_strict`, the result is checked with `Constraint::update_checked`.
+    pub fn merge(&self, constraint: &mut Constraint, row: &Constraint) -> Result<(), String>
+    {
+        if self.strict {
+            return constraint.update_checked(row, &self.guesses);
+        }
+        constraint.update(row);
+        Ok(())
+    }
+
+    /// Group `candidates` by the feedback `guess` gets against them, keyed by
+    /// `feedback_pattern`. This is how every scorer sees a guess.
+    pub fn partition<'a>(guess: &Word, candidates: &[&'a Word]) -> HashMap<String, Vec<&'a Word>>
+    {
+        let mut buckets: HashMap<String, Vec<&Word>> = HashMap::new();
+        for candidate in candidates {
+            buckets.entry(feedback_pattern(guess, candidate)).or_default().push(candidate);
+        }
+        buckets
+    }
+
+    /// Every guess that gets a different feedback pattern against each of
+    /// `candidates`, so whichever one is the answer, the guess after it wins.
+    /// Guesses that are candidates themselves come first, since they might
+    /// win straight away.
+    pub fn winning_guesses<'a>(&'a self, candidates: &[&Word]) -> Vec<&'a Word>
+    {
+        let mut winning: Vec<&Word> = self.guesses.par_iter()
+                .filter(|guess| WordleSolver::partition(guess, candidates).values().all(|bucket| bucket.len() == 1))
+                .collect();
+        winning.sort_by_key(|guess| !candidates.contains(guess));
+        winning
+    }
+
+    /// Play the first guess `best_guess` picks against every answer, and
+    /// report how many answers are left afterwards. The fewer, the easier
+    /// the word list is.
+    pub fn opener_stats(&self) -> Result<OpenerStats<'_>, String>
+    {
+        let constraint = Constraint::new(self.word_length());
+        let opener = self.choose_guess(&constraint, &[], false, true)?;
+        let answers: Vec<&Word> = self.answers.iter().collect();
+        let mut buckets = BTreeMap::new();
+        let mut total = 0;
+        for bucket in WordleSolver::partition(opener, &answers).values() {
+            *buckets.entry(bucket.len()).or_insert(0) += 1;
+            // Every answer in the bucket leaves all of them.
+            total += bucket.len() * bucket.len();
+        }
+        Ok(OpenerStats {
+            opener,
+            mean: total as f64 / answers.len() as f64,
+            max: buckets.keys().last().copied().unwrap_or(0),
+            buckets
+        })
+    }
+
+    /// Start a game, to be played a turn at a time.
+    pub fn new_game(&self) -> Game<'_>
+    {
+        Game::new(self, Constraint::new(self.word_length()))
+    }
+
+    /// The answers that satisfy `constraint`, without scoring anything.
+    /// Asking again about the same constraint doesn't filter them again.
+    pub fn remaining_words(&self, constraint: &Constraint) -> Vec<&Word>
+    {
+        let cached = self.remaining_cache.lock().unwrap().get(constraint).cloned();
+        // Filter without holding the lock, so other threads can use the cache
+        // in the meantime.
+        let indices = match cached {
+            Some(indices) => indices,
+            None => {
+                let indices = filter_indices(constraint, &self.answers);
+                self.remaining_cache.lock().unwrap().insert(constraint.clone(), indices.clone());
+                indices
+            }
+        };
+        indices.iter().map(|&i| &self.answers[i]).collect()
+    }
+
+    /// How many answers satisfy `constraint`.
+    pub fn remaining_count(&self, constraint: &Constraint) -> usize
+    {
+        self.remaining_words(constraint).len()
+    }
+
+    /// The index of the best first guess, once it has been computed.
+    pub fn first_guess(&self) -> Option<usize>
+    {
+        *self.first_guess.lock().unwrap()
+    }
+
+    pub fn best_guess<'a>(&'a self, constraint: &Constraint, verbose: bool) ->
+            Result<&'a Word, String>
+    {
+        self.choose_guess(constraint, &[], verbose, true)
+    }
+
+    /// Like `best_guess`, but if the solver excludes guessed words, none of
+    /// `guessed` is suggested again.
+    pub fn best_guess_excluding<'a>(&'a self, constraint: &Constraint, guessed: &[&Word],
+            verbose: bool) -> Result<&'a Word, String>
+    {
+        self.choose_guess(constraint, guessed, verbose, true)
+    }
+
+    /// Like `best_guess_excluding`, but `progress` controls whether a
+    /// progress bar is shown while scoring.
+    fn choose_guess<'a>(&'a self, constraint: &Constraint, guessed: &[&Word], verbose: bool,
+            progress: bool) -> Result<&'a Word, String>
+    {
+        let remaining_words = self.remaining_words(constraint);
+        self.choose_from(constraint, remaining_words, guessed, verbose, progress)
+    }
+
+    /// The guess with the most different vowels, then the most different
+    /// consonants, then the alphabetically first.
+    fn vowel_opener(&self) -> &Word
+    {
+        let distinct = |word: &Word, vowels: bool| {
+            word.chars().filter(|c| "aeiou".contains(*c) == vowels).collect::<HashSet<char>>().len()
+        };
+        self.guesses.iter()
+                .max_by_key(|guess| (distinct(guess, true), distinct(guess, false), cmp::Reverse(*guess)))
+                .unwrap()
+    }
+
+    /// Whichever of two words is more likely to be the answer: the one with
+    /// the bigger weight, or if the weights are the same, the one made of
+    /// letters that are more common in the answers, since that's the one a
+    /// person would more likely have picked. `first` wins if they're even.
+    fn more_common<'a>(&self, first: &'a Word, second: &'a Word) -> &'a Word
+    {
+        if first.weight() != second.weight() {
+            return if second.weight() > first.weight() { second } else { first };
+        }
+        let commonness = |word: &Word| -> usize {
+            word.chars().map(|c| self.letter_frequency.get(&c).copied().unwrap_or(0)).sum()
+        };
+        if commonness(second) > commonness(first) { second } else { first }
+    }
+
+    /// Like `choose_guess`, when the words that satisfy `constraint` are
+    /// already known.
+    pub(crate) fn choose_from<'a>(&'a self, constraint: &Constraint, remaining_words: Vec<&'a Word>,
+            guessed: &[&Word], verbose: bool, progress: bool) -> Result<&'a Word, String>
+    {
+        self.choose_scored(constraint, remaining_words, guessed, verbose, progress).map(|(guess, _score)| guess)
+    }
+
+    /// Like `choose_from`, but also return the score the guess got when it
+    /// was picked by scoring the guesses, rather than some other way.
+    fn choose_scored<'a>(&'a self, constraint: &Constraint, remaining_words: Vec<&'a Word>,
+            guessed: &[&Word], verbose: bool, progress: bool) -> Result<(&'a Word, Option<f64>), String>
+    {
+        let guessed = if self.exclude_guessed { guessed } else { &[] };
+
+        if remaining_words.len() == self.answers.len() && guessed.is_empty() {
+            match &self.opener {
+                OpenerStrategy::Score => (),
+                OpenerStrategy::Vowels => return Ok((self.vowel_opener(), None)),
+                OpenerStrategy::Word(word) => return self.find_word(word)
+                        .map(|word| (word, None))
+                        .ok_or(format!("Error: The opener '{}' isn't in the word list.", word))
+            }
+            let first_guess = self.first_guess.lock().unwrap();
+            if let Some(index) = *first_guess {
+                debug!("Using the known first guess {}", self.guesses[index].as_str());
+                return Ok((&self.guesses[index], None));
+            }
+        }
+
+        if remaining_words.is_empty() {
+            return Err("Error: No words match those constraints.".to_string());
+        }
+        if remaining_words.len() == 1 {
+            return Ok((remaining_words.first().unwrap(), None));
+        }
+        debug!("{}/{} words remaining", remaining_words.len(), self.answers.len());
+        let verbosity = if verbose { self.verbosity } else { Verbosity::Quiet };
+        if verbosity >= Verbosity::Status {
+            println!("{}/{} words remaining", remaining_words.len(), self.answers.len());
+        }
+        if verbosity >= Verbosity::Scores {
+            for w in &remaining_words {
+                println!("  {}  {}", w.as_str(), self.score_guess(w, constraint));
+            }
+        } else if verbosity >= Verbosity::Candidates && remaining_words.len() < CANDIDATE_LIST_THRESHOLD {
+            for w in &remaining_words {
+                println!("  {}", w.as_str())
+            }
+        }
+
+        if remaining_words.len() == 2 {
+            return Ok((self.more_common(remaining_words[0], remaining_words[1]), None));
+        }
+
+        if let Some(guess) = self.tree.as_ref().and_then(|tree| tree.lookup(&self.answers, &remaining_words)) {
+            if let Some(word) = self.find_word(guess).filter(|word| !guessed.contains(word)) {
+                debug!("Following the decision tree to {}", word.as_str());
+                return Ok((word, None));
+            }
+        }
+
+        if let Some(word) = self.opening_second_guess(&remaining_words).filter(|word| !guessed.contains(word)) {
+            return Ok((word, None));
+        }
+
+        // Looking ahead needs the real runners-up, so nothing can be pruned.
+        let lookahead = self.depth > 1 && remaining_words.len() <= LOOKAHEAD_WORDS;
+        let started = Instant::now();
+        let scores = self.score_guesses(&remaining_words, constraint, guessed, !lookahead, progress);
+        info!("Scored the guesses against {} words in {:.3}s", remaining_words.len(), started.elapsed().as_secs_f64());
+        let (best_score, best_allowed, mut best_guess, mut index) =
+            match scores.iter().max_by(|a, b| a.partial_cmp(b).unwrap()) {
+                Some(best) => *best,
+                // Excluding guessed words left nothing to score, so just
+                // guess one of the words that's left.
+                None if !guessed.is_empty() => return Ok((remaining_words.first().unwrap(), None)),
+                None => return Err("Error: No legal guesses left.".to_string())
+            };
+        let ties: Vec<_> = scores.iter()
+                .filter(|(score, allowed, _, _)| *score == best_score && *allowed == best_allowed)
+                .collect();
+        let default_tiebreak = if self.seed.is_some() { Tiebreak::Random } else { Tiebreak::Alpha };
+        match self.tiebreak.unwrap_or(default_tiebreak) {
+            Tiebreak::Alpha => (),
+            Tiebreak::Coverage => {
+                (_, _, best_guess, index) = **ties.iter()
+                        .max_by_key(|(_, _, guess, _)| (constraint.untested_letters(guess), *guess))
+                        .unwrap();
+            },
+            Tiebreak::Random => {
+                let mut rng = match self.seed {
+                    Some(seed) => StdRng::seed_from_u64(seed),
+                    None => StdRng::from_entropy()
+                };
+                (_, _, best_guess, index) = *ties[rng.gen_range(0..ties.len())];
+            }
+        }
+
+        if lookahead {
+            (best_guess, index) = self.lookahead_candidates(scores.clone())
+                    .into_par_iter()
+                    .map(|(guess, index)| {
+                        (self.expected_cost(guess, &remaining_words, constraint, self.depth - 1), guess, index)
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .min_by(|(a, _, _), (b, _, _)| a.partial_cmp(b).unwrap())
+                    .map(|(_, guess, index)| (guess, index))
+                    .unwrap();
+        }
+
+        if remaining_words.len() == self.answers.len() && guessed.is_empty() {
+            let mut first_guess = self.first_guess.lock().unwrap();
+            *first_guess = Some(index);
+        }
+
+        let score = scores.iter().find(|(_, _, _, i)| *i == index).map(|(score, _, _, _)| *score);
+        Ok((best_guess, score))
+    }
+
+    /// The guesses worth looking ahead from, best scoring first.
+    fn lookahead_candidates<'a>(&'a self, mut scores: Vec<(f64, bool, &'a Word, usize)>) ->
+            Vec<(&'a Word, usize)>
+    {
+        scores.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        scores.into_iter()
+                .take(LOOKAHEAD_BREADTH)
+                .map(|(_, _, guess, index)| (guess, index))
+                .collect()
+    }
+
+    /// The expected number of guesses it takes to find the answer among
+    /// `remaining` when `guess` is next, counting `guess`. After it, the best
+    /// few guesses are tried `depth` guesses deep, and from there on each
+    /// group of n words is guessed to take 1 + log4(n) more guesses.
+    fn expected_cost(&self, guess: &Word, remaining: &[&Word], constraint: &Constraint,
+            depth: usize) -> f64
+    {
+        let total: f64 = remaining.iter().map(|w| w.weight()).sum();
+        if total == 0.0 {
+            return 1.0;
+        }
+        let mut buckets = WordleSolver::partition(guess, remaining);
+        // Guessing the answer takes no more guesses.
+        buckets.remove(&feedback_pattern(guess, guess));
+        1.0 + buckets.into_values()
+                .map(|words| {
+                    let weight: f64 = words.iter().map(|w| w.weight()).sum();
+                    let mut next_constraint = constraint.clone();
+                    next_constraint.update(&wordle_guess(guess, words[0]));
+                    weight / total * self.solve_cost(&words, &next_constraint, depth)
+                })
+                .sum::<f64>()
+    }
+
+    /// The expected number of guesses it takes to find the answer among
+    /// `words`, looking `depth` guesses ahead.
+    fn solve_cost(&self, words: &Vec<&Word>, constraint: &Constraint, depth: usize) -> f64
+    {
+        if words.len() == 1 {
+            return 1.0;
+        }
+        if depth == 0 {
+            return 1.0 + (words.len() as f64).log2() / 2.0;
+        }
+        let scores = self.score_guesses(words, constraint, &[], false, false);
+        self.lookahead_candidates(scores).into_iter()
+                .map(|(guess, _)| self.expected_cost(guess, words, constraint, depth - 1))
+                .min_by(|a, b| a.partial_cmp(b).unwrap())
+                .unwrap_or(f64::INFINITY)
+    }
+
+    /// With an opening pair, the second guess to make when `remaining` are
+    /// the words left after the first guess.
+    fn opening_second_guess(&self, remaining: &[&Word]) -> Option<&Word>
+    {
+        if !self.opening_pair {
+            return None;
+        }
+        let first = self.opening_first_guess()?;
+        let pattern = feedback_pattern(first, remaining.first()?);
+        if remaining.iter().any(|w| feedback_pattern(first, w) != pattern) {
+            return None;
+        }
+        // Anything narrower than everything the first guess's feedback
+        // allows means there's more than one guess behind us.
+        let bucket = self.answers.iter().filter(|w| feedback_pattern(first, w) == pattern).count();
+        if bucket != remaining.len() {
+            return None;
+        }
+        // If the second guess can't tell the words apart, it was either
+        // made already or would be wasted.
+        let second = &self.guesses[self.second_guess()?];
+        let pattern = feedback_pattern(second, remaining[0]);
+        if remaining.iter().all(|w| feedback_pattern(second, w) == pattern) {
+            return None;
+        }
+        Some(second)
+    }
+
+    /// The first guess, whether it was scored or came from the decision
+    /// tree.
+    fn opening_first_guess(&self) -> Option<&Word>
+    {
+        match self.first_guess() {
+            Some(index) => Some(&self.guesses[index]),
+            None => self.find_word(&self.tree.as_ref()?.guess)
+        }
+    }
+
+    /// The index of the guess that always follows the first one with an
+    /// opening pair. Computed the first time it's needed, once the first
+    /// guess is known.
+    pub fn second_guess(&self) -> Option<usize>
+    {
+        let first = self.opening_first_guess()?;
+        let mut second_guess = self.second_guess.lock().unwrap();
+        if second_guess.is_none() {
+            let first_feedback: Vec<Vec<Feedback>> = self.answers.iter()
+                    .map(|answer| feedback(first, answer))
+                    .collect();
+            *second_guess = self.guesses.par_iter()
+                    .enumerate()
+                    .filter(|(_, guess)| *guess != first)
+                    .map(|(index, guess)| {
+                        let mut buckets: HashMap<(&Vec<Feedback>, Vec<Feedback>), Bucket> = HashMap::new();
+                        for (answer, first_feedback) in self.answers.iter().zip(first_feedback.iter()) {
+                            buckets.entry((first_feedback, feedback(guess, answer))).or_default().add(answer);
+                        }
+                        let buckets: Vec<Bucket> = buckets.into_iter()
+                                .map(|((first_feedback, feedback), bucket)| Bucket {
+                                    solved: [first_feedback, &feedback].iter()
+                                            .any(|pattern| pattern.iter().all(|f| *f == Feedback::Green)),
+                                    ..bucket
+                                })
+                                .collect();
+                        (self.scoring.score_buckets(&buckets), index)
+                    })
+                    .max_by(|a, b| a.partial_cmp(b).unwrap())
+                    .map(|(_, index)| index);
+        }
+        *second_guess
+    }
+
+    /// A decision tree that makes the first guess and then the opening
+    /// pair's second guess, whatever the feedback. Following it gives the
+    /// same guesses as an opening pair, so it can be cached instead.
+    pub fn opening_tree(&self) -> Option<DecisionTree>
+    {
+        let first = self.opening_first_guess()?;
+        let second = &self.guesses[self.second_guess()?];
+        let winning = feedback_pattern(first, first);
+        let answers: Vec<&Word> = self.answers.iter().collect();
+        let outcomes = WordleSolver::partition(first, &answers);
+        // Leave out the patterns where the second guess would be wasted, the
+        // same as `opening_second_guess` does.
+        let next = outcomes.into_iter()
+                .filter(|(pattern, _)| *pattern != winning)
+                .filter(|(_, words)| {
+                    let pattern = feedback_pattern(second, words[0]);
+                    words.iter().any(|w| feedback_pattern(second, w) != pattern)
+                })
+                .map(|(pattern, _)| (pattern, DecisionTree::new(second.as_str().to_string())))
+                .collect();
+        Some(DecisionTree { guess: first.as_str().to_string(), next })
+    }
+
+    /// Find `word` in the guess or answer list.
+    fn find_word(&self, word: &str) -> Option<&Word>
+    {
+        self.guesses.iter()
+                .chain(self.answers.iter())
+                .find(|w| w.as_str() == word)
+    }
+
+    /// Work out the best guess for every position the game can get into,
+    /// playing the way `best_guess` does.
+    pub fn build_decision_tree(&self) -> Result<DecisionTree, String>
+    {
+        let word_length = self.word_length();
+        self.build_subtree(&Constraint::new(word_length))
+    }
+
+    fn build_subtree(&self, constraint: &Constraint) -> Result<DecisionTree, String>
+    {
+        let guess = self.choose_guess(constraint, &[], false, false)?;
+        let remaining_words = self.remaining_words(constraint);
+
+        // One answer for each feedback pattern the guess can get, other than
+        // the one that wins.
+        let mut outcomes: HashMap<String, &Word> = HashMap::new();
+        for answer in remaining_words.iter().filter(|answer| **answer != guess) {
+            outcomes.entry(feedback_pattern(guess, answer)).or_insert(answer);
+        }
+        if outcomes.len() == 1 && !remaining_words.contains(&guess) {
+            return Err(format!("Guessing {} doesn't narrow down the {} remaining words.",
+                guess.as_str(), remaining_words.len()));
+        }
+
+        let next = outcomes.into_par_iter()
+                .map(|(pattern, answer)| {
+                    let mut next_constraint = constraint.clone();
+                    next_constraint.update(&wordle_guess(guess, answer));
+                    Ok((pattern, self.build_subtree(&next_constraint)?))
+                })
+                .collect::<Result<HashMap<_, _>, String>>()?;
+
+        Ok(DecisionTree { guess: guess.as_str().to_string(), next })
+    }
+
+    /// Return the `n` best guesses with their scores, best first. Guesses
+    /// with the same score are sorted alphabetically.
+    pub fn ranked_guesses<'a>(&'a self, constraint: &Constraint, n: usize) ->
+            Result<Vec<(&'a Word, f64)>, String>
+    {
+        let remaining_words = self.remaining_words(constraint);
+        if remaining_words.is_empty() {
+            return Err("Error: No words match those constraints.".to_string());
+        }
+
+        let mut scores = self.score_guesses(&remaining_words, constraint, &[], false, true);
+        scores.sort_by(|(score_a, allowed_a, guess_a, _), (score_b, allowed_b, guess_b, _)|
+                score_b.partial_cmp(score_a).unwrap()
+                        .then(allowed_b.cmp(allowed_a))
+                        .then(guess_a.cmp(guess_b)));
+        Ok(scores.into_iter()
+                .take(n)
+                .map(|(score, _allowed, guess, _index)| (guess, score))
+                .collect())
+    }
+
+    /// The opposite of `ranked_guesses`: the `n` lowest scoring guesses for
+    /// `constraint`, worst first. Guesses with the same score are in
+    /// alphabetical order.
+    pub fn worst_guesses<'a>(&'a self, constraint: &Constraint, n: usize) ->
+            Result<Vec<(&'a Word, f64)>, String>
+    {
+        let remaining_words = self.remaining_words(constraint);
+        if remaining_words.is_empty() {
+            return Err("Error: No words match those constraints.".to_string());
+        }
+
+        let mut scores = self.score_guesses(&remaining_words, constraint, &[], false, true);
+        scores.sort_by(|(score_a, _, guess_a, _), (score_b, _, guess_b, _)|
+                score_a.partial_cmp(score_b).unwrap().then(guess_a.cmp(guess_b)));
+        Ok(scores.into_iter()
+                .take(n)
+                .map(|(score, _allowed, guess, _index)| (guess, score))
+                .collect())
+    }
+
+    /// Score guesses against every answer with `score_guess_count_eliminations`
+    /// on all the threads, over and over, for at least `duration`. Returns
+    /// how many guesses were scored, and how long it really took.
+    pub fn bench_scoring(&self, duration: Duration) -> (usize, Duration)
+    {
+        let words: Vec<&Word> = self.answers.iter().collect();
+        let constraint = Constraint::new(self.word_length());
+        let batch = current_num_threads() * 4;
+        let started = Instant::now();
+        let mut scored = 0;
+        for guesses in self.guesses.chunks(batch).cycle() {
+            guesses.par_iter().for_each(|guess| {
+                hint::black_box(score_guess_count_eliminations(guess, &words, &constraint));
+            });
+            scored += guesses.len();
+            if started.elapsed() >= duration {
+                break;
+            }
+        }
+        (scored, started.elapsed())
+    }
+
+    /// With a feedback matrix, scoring works with the indices of the
+    /// answers that satisfy `constraint`. Without one, they aren't needed.
+    fn remaining_indices(&self, constraint: &Constraint) -> Vec<usize>
+    {
+        match self.matrix {
+            Some(_) => self.answers.iter()
+                    .enumerate()
+                    .filter(|(_index, answer)| constraint.allows(answer))
+                    .map(|(index, _answer)| index)
+                    .collect(),
+            None => Vec::new()
+        }
+    }
+
+    /// The score `best_guess` gives `guess`, including the bonus for words
+    /// that might be the answer.
+    pub fn score_guess(&self, guess: &Word, constraint: &Constraint) -> f64
+    {
+        let bonus = if constraint.allows(guess) { self.scoring.candidate_bonus() } else { 0.0 };
+        let index = self.guesses.iter().position(|g| g == guess);
+        let score = match (&self.matrix, index) {
+            (Some(matrix), Some(index)) =>
+                self.scoring.score_buckets(&matrix.buckets(index, &self.remaining_indices(constraint))),
+            _ => self.scoring.score(guess, &filter_words(constraint, &self.answers), constraint)
+        };
+        score + bonus
+    }
+
+    /// Work out the best first guess from scratch, the way `best_guess` does
+    /// for an empty board, and return it with the score it was picked by.
+    /// Afterwards `first_guess` returns it too.
+    pub fn best_opener(&self) -> Result<(&Word, f64), String>
+    {
+        let constraint = Constraint::new(self.word_length());
+        *self.first_guess.lock().unwrap() = None;
+        let remaining_words = filter_words(&constraint, &self.answers);
+        let (guess, score) = self.choose_scored(&constraint, remaining_words, &[], false, true)?;
+        // Scoring again could rank it differently, e.g. with exact_below.
+        Ok((guess, score.unwrap_or_else(|| self.score_guess(guess, &constraint))))
+    }
+
+    /// Score every legal guess against `remaining_words`, in guess list
+    /// order, skipping any in `excluded`. Each entry also records whether the
+    /// guess might be the answer, and its index in the guess list. With
+    /// `prune`, guesses that can't score at least as well as the best one
+    /// may be left out.
+    fn score_guesses<'a>(&'a self, remaining_words: &Vec<&Word>, constraint: &Constraint,
+            excluded: &[&Word], prune: bool, progress: bool) -> Vec<(f64, bool, &'a Word, usize)>
+    {
+        let candidates_only = self.hard_mode || remaining_words.len() <= self.endgame_threshold;
+        let pool: Vec<(usize, &Word)> = self.guesses.iter()
+                .enumerate()
+                .filter(|(_index, guess)| !candidates_only || constraint.allows(guess))
+                .filter(|(_index, guess)| !excluded.contains(guess))
+                .collect();
+
+        if self.exact_below.is_some_and(|exact_below| remaining_words.len() >= exact_below) {
+            let frequency = position_frequency(remaining_words);
+            return pool.par_iter()
+                    .map(|&(index, guess)| {
+                        (letter_frequency_score(guess, &frequency), constraint.allows(guess), guess, index)
+                    })
+                    .collect();
+        }
+
+        let remaining_indices = self.remaining_indices(constraint);
+
+        let bar = progress_bar(pool.len(), progress && !self.quiet);
+
+        // The best score so far, including the bonus, as the bits of an f64.
+        // Elimination scores are never negative, and non-negative floats
+        // order the same way as their bits.
+        let best = AtomicU64::new(0);
+        let scoring = self.scoring;
+        pool
+                .par_iter()
+                .progress_with(bar)
+                .filter_map(|&(index, guess)| {
+                    let allowed = constraint.allows(guess);
+                    // Prefer words that might be the answer.
+                    let bonus = if allowed { scoring.candidate_bonus() } else { 0.0 };
+                    let score = match &self.matrix {
+                        Some(matrix) => scoring.score_buckets(&matrix.buckets(index, &remaining_indices)),
+                        None if prune && scoring == Scoring::Eliminations => {
+                            let bound = f64::from_bits(best.load(Ordering::Relaxed)) - bonus;
+                            let score = score_guess_count_eliminations_above(
+                                guess, remaining_words, constraint, bound)?;
+                            best.fetch_max((score + bonus).to_bits(), Ordering::Relaxed);
+                            score
+                        },
+                        None => scoring.score(guess, remaining_words, constraint)
+                    };
+                    Some((score + bonus, allowed, guess, index))
+                })
+                .collect()
+    }
+
+    /// Play a game against `answer`, giving up after the maximum number of
+    /// guesses. If no word fits what the guesses showed, e.g. because the
+    /// answer isn't in the word list, the game is given up on right away,
+    /// with a note on stderr.
+    pub fn test<'a>(&'a self, answer: &Word, verbose: bool) -> GameResult<'a>
+    {
+        if answer.len() != self.word_length() {
+            eprintln!("Gave up on {}. It has {} letters, but the words have {}.", answer.as_str(), answer.len(),
+                self.word_length());
+            return GameResult::Failed(Vec::new());
+        }
+        let mut game = self.new_game().with_progress(verbose);
+        for _ in 0..self.max_guesses {
+            let guess = match game.suggest() {
+                Ok(guess) => guess,
+                Err(error) => {
+                    io::stdout().flush().unwrap();
+                    eprintln!("Gave up on {}. {}", answer.as_str(), error);
+                    return GameResult::Failed(game.guesses().to_vec());
+                }
+            };
+            if verbose {
+                print_guess(guess, answer);
+                if self.timing {
+                    game.print_time();
+                }
+            }
+            let before = game.remaining().len();
+            if let Err(error) = game.observe(guess, answer) {
+                io::stdout().flush().unwrap();
+                eprintln!("Gave up on {}. {}", answer.as_str(), error);
+                return GameResult::Failed(game.guesses().to_vec());
+            }
+            if verbose && self.verbosity >= Verbosity::Status {
+                let after = game.remaining().len();
+                println!("  Ruled out {} of {} words, {} left", before - after, before, after);
+            }
+            if game.is_solved() {
+                return GameResult::Solved(game.guesses().to_vec());
+            }
+        }
+        GameResult::Failed(game.guesses().to_vec())
+    }
+
+    /// Check that `path`, a list of guesses ending with the answer, is a game
+    /// that could have been played: every word is in the guess list, the
+    /// last one is a possible answer, and the game doesn't end any earlier.
+    /// In hard mode every guess must also satisfy what came before it. With
+    /// `verbose`, each guess is printed the way `test` shows it.
+    pub fn verify<'a>(&'a self, path: &[&str], verbose: bool) -> Result<Vec<&'a Word>, String>
+    {
+        let last = path.last().ok_or("There are no guesses to verify.")?;
+        let answer = self.answers.iter()
+                .find(|answer| answer.as_str() == *last)
+                .ok_or(format!("'{}' isn't one of the answers in the word list.", last))?;
+        let mut constraint = Constraint::new(self.word_length());
+        let mut result = Vec::new();
+        for (i, word) in path.iter().enumerate() {
+            let guess = match self.guesses.iter().find(|guess| guess.as_str() == *word) {
+                Some(guess) => guess,
+                // The answer may always be guessed, even if it isn't in the
+                // guess list.
+                None if answer.as_str() == *word => answer,
+                None => return Err(format!("'{}' isn't in the word list.", word))
+            };
+            if self.hard_mode && !constraint.allows(guess) {
+                return Err(format!("'{}' doesn't fit the earlier guesses, which hard mode requires.", word));
+            }
+            if verbose {
+                print_guess(guess, answer);
+            }
+            result.push(guess);
+            if guess == answer && i + 1 < path.len() {
+                return Err(format!("The game was already won after {} guesses.", i + 1));
+            }
+            constraint.update(&wordle_guess(guess, answer));
+        }
+        Ok(result)
+    }
+
+    /// Pick a guess for several boards at once, the way Dordle is played.
+    /// Each guess is scored by the words it eliminates summed over the boards
+    /// that haven't been solved yet. Once only one board is left, this is
+    /// just `best_guess` for that board. It's an error if every board is
+    /// solved already.
+    pub fn best_dordle_guess<'a>(&'a self, boards: &[Constraint]) -> Result<&'a Word, String>
+    {
+        let unsolved: Vec<&Constraint> = boards.iter().filter(|b| !b.is_solved()).collect();
+        if unsolved.is_empty() {
+            return Err("Error: Every board is solved already.".to_string());
+        }
+        if unsolved.len() == 1 {
+            return self.choose_guess(unsolved[0], &[], false, false);
+        }
+        let remaining: Vec<Vec<&Word>> = unsolved.iter()
+                .map(|constraint| filter_words(constraint, &self.answers))
+                .collect();
+        if remaining.iter().any(|words| words.is_empty()) {
+            return Err("Error: No words match those constraints.".to_string());
+        }
+        // A board with only one word left can be solved right away.
+        if let Some(words) = remaining.iter().find(|words| words.len() == 1) {
+            return Ok(words[0]);
+        }
+
+        self.guesses
+                .par_iter()
+                .map(|guess| {
+                    let score: f64 = unsolved.iter().zip(remaining.iter())
+                            .map(|(constraint, words)| score_guess_count_eliminations(guess, words, constraint))
+                            .sum();
+                    // Prefer words that might be one of the answers.
+                    let allowed = unsolved.iter().any(|constraint| constraint.allows(guess));
+                    (score + if allowed { 1.0 } else { 0.0 }, guess)
+                })
+                .max_by(|a, b| a.partial_cmp(b).unwrap())
+                .map(|(_score, guess)| guess)
+                .ok_or_else(|| "Error: No legal guesses left.".to_string())
+    }
+
+    /// Play Dordle against `answers`, returning the guesses it took to solve
+    /// all of them, or an error if they aren't all solved within the maximum
+    /// number of guesses.
+    pub fn test_dordle<'a>(&'a self, answers: &[&Word], verbose: bool) -> Result<Vec<&'a Word>, String>
+    {
+        let mut result = Vec::new();
+        let word_length = self.word_length();
+        let mut boards = vec![Constraint::new(word_length); answers.len()];
+        for _ in 0..self.max_guesses {
+            let guess = self.best_dordle_guess(&boards)?;
+            result.push(guess);
+            if verbose {
+                println!("Guess: {}", guess.as_str());
+            }
+            for (board, answer) in boards.iter_mut().zip(answers.iter()) {
+                board.update(&wordle_guess(guess, answer));
+            }
+            if boards.iter().all(|board| board.is_solved()) {
+                return Ok(result);
+            }
+        }
+        Err(format!("Gave up after {} guesses.", self.max_guesses))
+    }
+
+    /// Play Antiwordle against `answer`: every guess has to satisfy what's
+    /// been revealed so far, and the goal is to last as long as possible
+    /// before guessing the answer. Each turn picks the legal word, not
+    /// guessed before, with the worst score. Returns the guesses, ending with
+    /// the answer.
+    pub fn anti<'a>(&'a self, answer: &Word, verbose: bool) -> Vec<&'a Word>
+    {
+        let mut result: Vec<&Word> = Vec::new();
+        let word_length = self.word_length();
+        let mut constraint = Constraint::new(word_length);
+        loop {
+            let remaining_words = filter_words(&constraint, &self.answers);
+            let guess = self.score_guesses(&remaining_words, &constraint, &result, false, false)
+                    .into_iter()
+                    .filter(|(_score, allowed, _guess, _index)| *allowed)
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .map(|(_score, _allowed, guess, _index)| guess);
+            let guess = match guess {
+                Some(guess) => guess,
+                // Only the answer is left, and it isn't in the guess list.
+                None => match self.answers.iter().find(|w| *w == answer) {
+                    Some(guess) => guess,
+                    None => return result
+                }
+            };
+            result.push(guess);
+            if verbose {
+                print_guess(guess, answer);
+            }
+            if guess == answer {
+                return result;
+            }
+            constraint.update(&wordle_guess(guess, answer));
+        }
+    }
+
+    /// Play against an adversary that never commits to an answer, the way
+    /// Absurdle does. After each guess it keeps whichever feedback pattern
+    /// leaves the most words, preferring fewer greens and then fewer yellows
+    /// when that's a tie. Returns the guesses it took to pin down the answer,
+    /// or an error if that takes more than the maximum number of guesses.
+    pub fn absurdle(&self, verbose: bool) -> Result<Vec<&Word>, String>
+    {
+        let mut result = Vec::new();
+        let word_length = self.word_length();
+        let mut constraint = Constraint::new(word_length);
+        for _ in 0..self.max_guesses {
+            let guess = self.choose_guess(&constraint, &result, verbose, false)?;
+            result.push(guess);
+
+            let buckets = WordleSolver::partition(guess, &filter_words(&constraint, &self.answers));
+            let (pattern, words) = buckets.into_iter()
+                    .max_by_key(|(pattern, words)| (
+                        words.len(),
+                        cmp::Reverse(pattern.matches('G').count()),
+                        cmp::Reverse(pattern.matches('Y').count()),
+                        cmp::Reverse(pattern.clone())))
+                    .unwrap();
+            if verbose {
+                println!("Guess: {} {} ({} words left)", guess.as_str(), pattern, words.len());
+            }
+            if !pattern.contains(['X', 'Y']) {
+                return Ok(result);
+            }
+            constraint.update(&wordle_guess(guess, words[0]));
+        }
+        Err(format!("Gave up after {} guesses.", self.max_guesses))
+    }
+
+    /// Solve every answer. When `verbose` is set, print the guesses made
+    /// for each one.
+    pub fn full_test(&self, verbose: bool) -> Summary
+    {
+        self.test_answers(&self.answers, verbose, None).unwrap()
+    }
+
+    /// Like `full_test`, but also write a CSV file to `csv` with a row for
+    /// each answer: the word, how many guesses were made, and the guesses
+    /// separated by `|`. Each row is written and flushed as soon as its game
+    /// is over, so the rows are in no particular order.
+    pub fn full_test_csv(&self, verbose: bool, csv: &mut (dyn Write + Send)) -> Result<Summary, String>
+    {
+        self.test_answers(&self.answers, verbose, Some(csv))
+    }
+
+    /// Like `full_test`, but only play the games for `answers`, e.g. the
+    /// words that have actually been wordle answers. Each of them has to be
+    /// one of the solver's answers. If `csv` is given, the games are written
+    /// to it the way `full_test_csv` does.
+    pub fn test_answers(&self, answers: &[Word], verbose: bool, csv: Option<&mut (dyn Write + Send)>) ->
+            Result<Summary, String>
+    {
+        self.test_answers_with(answers, verbose, csv, &|_, _| ())
+    }
+
+    /// Like `test_answers`, but also call `on_game` with each game as soon as
+    /// it's over. Games are played in parallel, so they finish in no
+    /// particular order.
+    pub fn test_answers_with(&self, answers: &[Word], verbose: bool, mut csv: Option<&mut (dyn Write + Send)>,
+            on_game: &(dyn Fn(&Word, &GameResult) + Sync)) -> Result<Summary, String>
+    {
+        if let Some(answer) = answers.iter().find(|answer| !self.answers.contains(answer)) {
+            return Err(format!("'{}' isn't one of the answers in the word list.", answer.as_str()));
+        }
+
+        let write_error = |error: io::Error| format!("Failed to write CSV: {}", error);
+        if let Some(csv) = csv.as_mut() {
+            writeln!(csv, "word,guesses,sequence").and_then(|()| csv.flush()).map_err(write_error)?;
+        }
+
+        // Every game starts with the same guess. Work it out once up front so
+        // the games below don't all race to compute it.
+        if !answers.is_empty() {
+            self.best_guess(&Constraint::new(self.word_length()), false).unwrap();
+            if self.opening_pair {
+                self.second_guess();
+            }
+        }
+
+        // The bar moves on once per game, with an estimate of how long the
+        // rest will take.
+        let bar = progress_bar(answers.len(), !self.quiet);
+        let csv = Mutex::new(csv);
+        let csv_error = Mutex::new(None);
+        let results: Vec<GameResult> = answers
+                .par_iter()
+                .progress_with(bar)
+                .map(|word| {
+                    let result = self.test(word, false);
+                    on_game(word, &result);
+                    // Flush every row, so a full test that's cut short still
+                    // has the games that were over.
+                    if let Some(csv) = csv.lock().unwrap().as_mut() {
+                        let sequence: Vec<&str> = result.guesses().iter().map(|g| g.as_str()).collect();
+                        if let Err(error) = writeln!(csv, "{},{},{}", word.as_str(), sequence.len(), sequence.join("|"))
+                                .and_then(|()| csv.flush()) {
+                            csv_error.lock().unwrap().get_or_insert(write_error(error));
+                        }
+                    }
+                    result
+                })
+                .collect();
+        if let Some(error) = csv_error.into_inner().unwrap() {
+            return Err(error);
+        }
+
+        let mut summary = Summary::new();
+        for (word, result) in answers.iter().zip(results) {
+            match &result {
+                GameResult::Solved(guesses) => summary.add(guesses.len()),
+                GameResult::Failed(_) => summary.add_unsolved()
+            }
+            if !result.is_solved() || result.guesses().len() > MAX_GUESSES {
+                let guesses: Vec<&str> = result.guesses().iter().map(|g| g.as_str()).collect();
+                summary.add_failed_game(word.as_str(), &guesses);
+            }
+            if verbose {
+                match result {
+                    GameResult::Solved(_) => print!("Guessed {} from", word.as_str()),
+                    GameResult::Failed(_) => print!("Failed to guess {} from", word.as_str())
+                }
+                for guess in result.guesses() {
+                    print!(" {}", guess.as_str());
+                }
+                println!();
+            }
+        }
+        Ok(summary)
+    }
+}
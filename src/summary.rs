@@ -0,0 +1,159 @@
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Games that take more guesses than this are lost.
+pub const MAX_GUESSES: usize = 6;
+
+/// How wide the longest bar in the histogram is.
+const HISTOGRAM_WIDTH: usize = 50;
+
+/// How many guesses it took to solve each of a set of answers.
+#[derive(Debug, Default)]
+pub struct Summary {
+    /// How many guesses each game took, in the order they were added, or
+    /// `None` for games that were given up on before finding the answer.
+    games: Vec<Option<usize>>,
+    /// The answer and guesses of every game that counts as a failure.
+    failed_games: Vec<(String, Vec<String>)>
+}
+
+impl Summary {
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn add(&mut self, guess_count: usize)
+    {
+        self.games.push(Some(guess_count));
+    }
+
+    /// Record a game that ran out of guesses. It counts as a failure, but
+    /// not towards the mean, median or max.
+    pub fn add_unsolved(&mut self)
+    {
+        self.games.push(None);
+    }
+
+    /// Remember the guesses of a failed game, to list them with the
+    /// summary. This doesn't count the game; `add` or `add_unsolved` do.
+    pub fn add_failed_game(&mut self, answer: &str, guesses: &[&str])
+    {
+        let guesses = guesses.iter().map(|g| g.to_string()).collect();
+        self.failed_games.push((answer.to_string(), guesses));
+    }
+
+    pub fn failed_games(&self) -> &[(String, Vec<String>)]
+    {
+        &self.failed_games
+    }
+
+    pub fn unsolved(&self) -> usize
+    {
+        self.games.iter().filter(|game| game.is_none()).count()
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.games.len()
+    }
+
+    /// How many guesses each solved game took.
+    fn guess_counts(&self) -> Vec<usize>
+    {
+        self.games.iter().flatten().copied().collect()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.len() == 0
+    }
+
+    pub fn mean(&self) -> f64
+    {
+        let guess_counts = self.guess_counts();
+        if guess_counts.is_empty() {
+            return 0.0;
+        }
+        guess_counts.iter().sum::<usize>() as f64 / guess_counts.len() as f64
+    }
+
+    pub fn median(&self) -> f64
+    {
+        let mut sorted = self.guess_counts();
+        if sorted.is_empty() {
+            return 0.0;
+        }
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) as f64 / 2.0
+        } else {
+            sorted[middle] as f64
+        }
+    }
+
+    pub fn max(&self) -> usize
+    {
+        self.games.iter().flatten().copied().max().unwrap_or(0)
+    }
+
+    /// How many answers were solved in each number of guesses, from 1 up to
+    /// the max.
+    pub fn histogram(&self) -> Vec<usize>
+    {
+        let mut counts = vec![0; self.max()];
+        for count in self.games.iter().flatten() {
+            counts[count - 1] += 1;
+        }
+        counts
+    }
+
+    /// How many answers took more than `MAX_GUESSES` guesses, or weren't
+    /// found at all.
+    pub fn failures(&self) -> usize
+    {
+        self.games.iter().filter(|game| game.is_none_or(|count| count > MAX_GUESSES)).count()
+    }
+
+    /// A SHA-256 hash of how many guesses every game took, in order, so two
+    /// runs can be compared at a glance. Any game that takes a different
+    /// number of guesses changes it.
+    pub fn digest(&self) -> String
+    {
+        let games: Vec<String> = self.games.iter()
+                .map(|game| game.map_or("-".to_string(), |count| count.to_string()))
+                .collect();
+        hex::encode(Sha256::digest(games.join(",")))
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        writeln!(f, "Words:    {}", self.len())?;
+        writeln!(f, "Mean:     {:.3}", self.mean())?;
+        writeln!(f, "Median:   {}", self.median())?;
+        writeln!(f, "Max:      {}", self.max())?;
+        write!(f, "Failures: {} (more than {} guesses)", self.failures(), MAX_GUESSES)?;
+        if self.unsolved() > 0 {
+            write!(f, "\nUnsolved: {}", self.unsolved())?;
+        }
+        let histogram = self.histogram();
+        let most = histogram.iter().copied().max().unwrap_or(0);
+        if most > 0 {
+            writeln!(f)?;
+        }
+        for (i, count) in histogram.iter().enumerate() {
+            let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(most));
+            write!(f, "\n{:>2} {:<width$} {}", i + 1, bar, count, width = HISTOGRAM_WIDTH)?;
+        }
+        if !self.failed_games.is_empty() {
+            write!(f, "\n\nFAILURES")?;
+            for (answer, guesses) in &self.failed_games {
+                write!(f, "\n{}: {}", answer, guesses.join(" "))?;
+            }
+        }
+        Ok(())
+    }
+}
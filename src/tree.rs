@@ -0,0 +1,51 @@
+use crate::constraint::feedback_pattern;
+use crate::word::Word;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The guess to make at each point in a game, keyed by the feedback pattern
+/// each earlier guess got, so it can be saved and loaded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionTree {
+    pub guess: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub next: HashMap<String, DecisionTree>
+}
+
+impl DecisionTree {
+    pub fn new(guess: String) -> Self
+    {
+        Self { guess, next: HashMap::new() }
+    }
+
+    /// How many guesses the tree knows about.
+    pub fn node_count(&self) -> usize
+    {
+        1 + self.next.values().map(|tree| tree.node_count()).sum::<usize>()
+    }
+
+    /// Find the guess to make when `remaining` are the words left out of
+    /// `answers`. That's only known if the game so far followed the tree,
+    /// leaving exactly the same words as one of its nodes.
+    pub(crate) fn lookup(&self, answers: &[Word], remaining: &[&Word]) -> Option<&str>
+    {
+        let mut node = self;
+        let mut words: Vec<&Word> = answers.iter().collect();
+        loop {
+            if words.len() == remaining.len() {
+                if words.iter().zip(remaining.iter()).all(|(a, b)| a == b) {
+                    return Some(&node.guess);
+                }
+                return None;
+            }
+
+            let guess = Word::new(node.guess.clone());
+            let pattern = feedback_pattern(&guess, remaining.first()?);
+            if remaining.iter().any(|w| feedback_pattern(&guess, w) != pattern) {
+                return None;
+            }
+            node = node.next.get(&pattern)?;
+            words.retain(|w| feedback_pattern(&guess, w) == pattern);
+        }
+    }
+}
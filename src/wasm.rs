@@ -0,0 +1,25 @@
+//! The solver for JavaScript, through `wasm_bindgen`, so it can run in the
+//! browser with no server.
+
+use crate::constraint::Constraint;
+use crate::scoring::Scoring;
+use crate::solver::WordleSolver;
+use crate::word::read_words_from_str;
+use wasm_bindgen::prelude::*;
+
+/// The best guess for `rows`, written the way the command line takes them,
+/// e.g. `-r -a ~i -s -e`. `words` is the whole word list, one word per line,
+/// since there's no file to read it from. Nothing is cached, so the first
+/// guess is scored every time.
+#[wasm_bindgen]
+pub fn best_guess_from_constraints(words: &str, rows: Vec<String>) -> Result<String, String>
+{
+    let words = read_words_from_str(words, None)?.words;
+    let word_length = words.first().ok_or("The word list is empty.")?.len();
+    let solver = WordleSolver::new(words, None, None, Scoring::Eliminations).with_quiet(true);
+    let mut constraint = Constraint::new(word_length);
+    for row in &rows {
+        solver.merge(&mut constraint, &Constraint::from_string(row, word_length)?)?;
+    }
+    Ok(solver.best_guess(&constraint, false)?.as_str().to_string())
+}
@@ -0,0 +1,364 @@
+use sha2::{Sha256, Digest};
+use std::cmp;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::str::Chars;
+
+/// The longest word that gets the fast ASCII representation.
+const MAX_ASCII_LENGTH: usize = 16;
+
+/// How the letters of a word are stored. Words made only of lowercase ASCII
+/// letters, which is nearly every dictionary, use fixed size arrays so that
+/// counting a letter is an array index instead of a hash lookup.
+#[derive(Clone, PartialEq, Eq)]
+enum Letters {
+    Ascii {
+        bytes: [u8; MAX_ASCII_LENGTH],
+        // How often each letter from a to z occurs.
+        frequency: [u8; 26]
+    },
+    Unicode(HashMap<char, usize>)
+}
+
+/// A single dictionary word. Its contents can't be changed after
+/// construction.
+#[derive(Clone)]
+pub struct Word {
+    word: String,
+    letters: Letters,
+    /// How likely the word is to be the answer, relative to the others.
+    weight: f64
+}
+
+impl PartialEq for Word {
+    fn eq(&self, other: &Self) -> bool {
+        self.word == other.word
+    }
+}
+
+impl Eq for Word {}
+
+impl Ord for Word{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.word.cmp(&other.word)
+    }
+}
+
+impl PartialOrd for Word{
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Word {
+    pub fn new(word: String) -> Self
+    {
+        let letters = if word.len() <= MAX_ASCII_LENGTH &&
+                word.bytes().all(|b| b.is_ascii_lowercase()) {
+            let mut bytes = [0; MAX_ASCII_LENGTH];
+            let mut frequency = [0; 26];
+            for (i, b) in word.bytes().enumerate() {
+                bytes[i] = b;
+                frequency[(b - b'a') as usize] += 1;
+            }
+            Letters::Ascii { bytes, frequency }
+        } else {
+            Letters::Unicode(char_frequency(word.chars()))
+        };
+        Self { word, letters, weight: 1.0 }
+    }
+
+    /// Set how likely the word is to be the answer. Words are weighted 1 by
+    /// default.
+    pub fn with_weight(mut self, weight: f64) -> Self
+    {
+        self.weight = weight;
+        self
+    }
+
+    pub fn weight(&self) -> f64
+    {
+        self.weight
+    }
+
+    pub fn as_str(&self) -> &str
+    {
+        &self.word
+    }
+
+    pub fn char_count(&self, c: &char) -> usize
+    {
+        match &self.letters {
+            Letters::Ascii { frequency, .. } => if c.is_ascii_lowercase() {
+                frequency[(*c as u8 - b'a') as usize] as usize
+            } else {
+                0
+            },
+            Letters::Unicode(frequency) => *frequency.get(c).unwrap_or(&0)
+        }
+    }
+
+    /// The word's bytes, if it only has lowercase ASCII letters.
+    pub(crate) fn ascii(&self) -> Option<&[u8]>
+    {
+        match &self.letters {
+            Letters::Ascii { bytes, .. } => Some(&bytes[..self.word.len()]),
+            Letters::Unicode(_) => None
+        }
+    }
+
+    /// How often each letter from a to z occurs, if the word only has
+    /// lowercase ASCII letters.
+    pub(crate) fn ascii_frequency(&self) -> Option<&[u8; 26]>
+    {
+        match &self.letters {
+            Letters::Ascii { frequency, .. } => Some(frequency),
+            Letters::Unicode(_) => None
+        }
+    }
+
+    pub fn chars(&self) -> Chars<'_>
+    {
+        self.word.chars()
+    }
+
+    /// The number of letters, i.e. `char`s, in the word. Not its length in
+    /// bytes.
+    pub fn len(&self) -> usize
+    {
+        match self.letters {
+            Letters::Ascii { .. } => self.word.len(),
+            Letters::Unicode(_) => self.word.chars().count()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.word.is_empty()
+    }
+}
+
+pub(crate) fn char_frequency(chars: impl Iterator<Item = char>) -> HashMap<char, usize>
+{
+    let mut char_frequency = HashMap::new();
+    for c in chars {
+        char_frequency.entry(c).and_modify(|n| *n += 1).or_insert(1);
+    }
+    char_frequency
+}
+
+/// Count how often each letter appears at each position in `words`.
+pub fn position_frequency(words: &[&Word]) -> Vec<HashMap<char, usize>>
+{
+    let word_length = words.first().map_or(0, |w| w.len());
+    let mut frequency = vec![HashMap::new(); word_length];
+    for word in words {
+        for (counts, c) in frequency.iter_mut().zip(word.chars()) {
+            counts.entry(c).and_modify(|n| *n += 1).or_insert(1);
+        }
+    }
+    frequency
+}
+
+/// The contents of a word list file.
+pub struct WordList {
+    pub words: Vec<Word>,
+    /// SHA-256 hash of the words, used to key the cache.
+    pub hash: String,
+    /// How many lines were skipped because they were the wrong length.
+    pub skipped: usize,
+    /// How many lines were left out because the same word came earlier.
+    pub duplicates: usize
+}
+
+/// The contents of a weights file.
+pub struct WeightList {
+    pub weights: HashMap<String, f64>,
+    /// SHA-256 hash of the file, used to key the cache.
+    pub hash: String
+}
+
+/// Read a weights file, with one `word,weight` pair per line. Blank lines are
+/// skipped, and weights can't be negative.
+pub fn read_weights(path: &str) -> Result<WeightList, String>
+{
+    let file = File::open(path).map_err(|error| format!("Failed to open {}: {}", path, error))?;
+    let mut weights = HashMap::new();
+    let mut hasher = Sha256::new();
+    for line_result in io::BufReader::new(file).lines() {
+        let line = line_result.map_err(|error| format!("Failed to read {}: {}", path, error))?;
+        if line.is_empty() {
+            continue;
+        }
+        let weight = line.split_once(',')
+                .and_then(|(word, weight)| Some((word, weight.trim().parse::<f64>().ok()?)))
+                .filter(|(_word, weight)| weight.is_finite() && *weight >= 0.0);
+        match weight {
+            Some((word, weight)) => weights.insert(word.to_string(), weight),
+            None => return Err(format!("Line '{}' in {} should be a word, a comma and a weight of 0 or more.",
+                line, path))
+        };
+        hasher.update(&line);
+    }
+    Ok(WeightList { weights, hash: hex::encode(hasher.finalize()) })
+}
+
+/// Read a word list with one word per line, skipping blank lines. A `path`
+/// of `-` reads from stdin. If `word_length` is given, lines of any other
+/// length are skipped too. Otherwise the first word sets the length, and any
+/// line that doesn't match it is an error.
+///
+/// Lengths count `char`s, so every letter has to be a single `char`. Accented
+/// letters like `é` should be precomposed (Unicode NFC); an `e` followed by a
+/// combining accent counts as two letters.
+pub fn read_words(path: &str, word_length: Option<usize>) -> Result<WordList, String>
+{
+    parse_words(open_words(path)?, path, word_length)
+}
+
+/// Open a word list file, or stdin for `-`.
+fn open_words(path: &str) -> Result<Box<dyn BufRead>, String>
+{
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    match File::open(path) {
+        Ok(file) => Ok(Box::new(io::BufReader::new(file))),
+        Err(error) => Err(format!("Failed to open {}: {}", path, error))
+    }
+}
+
+/// The lines of a word list that aren't blank.
+fn word_lines<'a>(reader: impl BufRead + 'a, name: &'a str) -> impl Iterator<Item = Result<String, String>> + 'a
+{
+    reader.lines()
+            .map(move |line| line.map_err(|error| format!("Failed to read {}: {}", name, error)))
+            .filter(|line| line.as_ref().map_or(true, |line| !line.is_empty()))
+}
+
+/// Like `read_words`, but for a word list that's already in memory, e.g. one
+/// built into the program. The hash is the same as for a file holding `text`.
+pub fn read_words_from_str(text: &str, word_length: Option<usize>) -> Result<WordList, String>
+{
+    parse_words(text.as_bytes(), "the word list", word_length)
+}
+
+/// Read words from `reader`, the way `read_words` describes. `name` says
+/// where they come from in errors.
+fn parse_words(reader: impl BufRead, name: &str, word_length: Option<usize>) -> Result<WordList, String>
+{
+    let mut words = Vec::new();
+    let mut hasher = Sha256::new();
+    let mut skipped = 0;
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
+
+    let expected_length = word_length;
+    let mut word_length = word_length;
+    for line in word_lines(reader, name) {
+        let line = line?;
+        let l1 = line.chars().count();
+        match word_length {
+            Some(l2) => if l1 != l2 {
+                if expected_length.is_some() {
+                    skipped += 1;
+                    continue;
+                }
+                return Err(format!("Some lines in {} contain {} characters while others contain {} characters (e.g. {}).",
+                    name, l1, l2, line));
+            },
+            None => word_length = Some(l1)
+        }
+        // Duplicates still count towards the hash, so the cache key only
+        // depends on what's in the file.
+        hasher.update(&line);
+        if !seen.insert(line.clone()) {
+            duplicates += 1;
+            continue;
+        }
+        words.push(Word::new(line));
+    }
+    Ok(WordList { words, hash: hex::encode(hasher.finalize()), skipped, duplicates })
+}
+
+/// What's wrong, if anything, with a word list, from `check_words`.
+pub struct DictionaryCheck {
+    /// How many words there are, duplicates and all.
+    pub words: usize,
+    /// How many words there are of each length.
+    pub lengths: BTreeMap<usize, usize>,
+    /// How many words came earlier in the list too.
+    pub duplicates: usize,
+    /// How many words have something other than letters in them, with the
+    /// first one.
+    pub non_alphabetic: usize,
+    pub first_non_alphabetic: Option<String>
+}
+
+impl DictionaryCheck {
+    /// Whether the list can be used: it has words, and they're all the
+    /// same length. Duplicates and other characters are only warned about.
+    pub fn is_ok(&self) -> bool
+    {
+        self.words > 0 && self.lengths.len() == 1
+    }
+}
+
+impl fmt::Display for DictionaryCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let lengths: Vec<String> = self.lengths.iter()
+                .map(|(length, count)| format!("{} ({} word{})", length, count, if *count == 1 { "" } else { "s" }))
+                .collect();
+        writeln!(f, "Words:          {}", self.words)?;
+        writeln!(f, "Lengths:        {}", lengths.join(", "))?;
+        writeln!(f, "Same length:    {}", if self.lengths.len() <= 1 { "yes" } else { "no" })?;
+        writeln!(f, "Duplicates:     {}", self.duplicates)?;
+        write!(f, "Non-alphabetic: {}", self.non_alphabetic)?;
+        if let Some(word) = &self.first_non_alphabetic {
+            write!(f, " (e.g. {})", word)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a word list for problems, reporting all of them instead of stopping
+/// at the first like `read_words` does. A `path` of `-` reads from stdin.
+/// Only failing to read the file is an error.
+pub fn check_words(path: &str) -> Result<DictionaryCheck, String>
+{
+    check_lines(open_words(path)?, path)
+}
+
+/// Like `check_words`, but for a word list that's already in memory.
+pub fn check_words_from_str(text: &str) -> Result<DictionaryCheck, String>
+{
+    check_lines(text.as_bytes(), "the word list")
+}
+
+fn check_lines(reader: impl BufRead, name: &str) -> Result<DictionaryCheck, String>
+{
+    let mut check = DictionaryCheck {
+        words: 0,
+        lengths: BTreeMap::new(),
+        duplicates: 0,
+        non_alphabetic: 0,
+        first_non_alphabetic: None
+    };
+    let mut seen = HashSet::new();
+    for line in word_lines(reader, name) {
+        let line = line?;
+        check.words += 1;
+        *check.lengths.entry(line.chars().count()).or_insert(0) += 1;
+        if !line.chars().all(char::is_alphabetic) {
+            check.non_alphabetic += 1;
+            check.first_non_alphabetic.get_or_insert_with(|| line.clone());
+        }
+        if !seen.insert(line) {
+            check.duplicates += 1;
+        }
+    }
+    Ok(check)
+}
@@ -0,0 +1,20 @@
+mod common;
+
+use common::small_solver;
+use wordle_solve::{wordle_guess, Constraint};
+
+#[test]
+fn anti_only_makes_legal_guesses_and_ends_on_the_answer()
+{
+    let solver = small_solver(100).with_feedback_matrix(true);
+    let answer = solver.answers().iter().find(|w| w.as_str() == "adobe").unwrap();
+
+    let guesses = solver.anti(answer, false);
+    assert!(*guesses.last().unwrap() == answer);
+    let mut constraint = Constraint::new(5);
+    for (i, guess) in guesses.iter().enumerate() {
+        assert!(constraint.allows(guess), "{} breaks the rules", guess.as_str());
+        assert!(!guesses[..i].contains(guess), "{} was guessed twice", guess.as_str());
+        constraint.update(&wordle_guess(guess, answer));
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "parallel")]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command};
+
+fn wordle_solve(args: &[&str]) -> std::process::Output
+{
+    wordle_solve_in(Path::new(env!("CARGO_MANIFEST_DIR")), args)
+}
+
+/// Run in `dir`, which decides whether there's a `words` file to use.
+fn wordle_solve_in(dir: &Path, args: &[&str]) -> std::process::Output
+{
+    Command::new(env!("CARGO_BIN_EXE_wordle-solve"))
+            .current_dir(dir)
+            .arg("--no-cache")
+            .args(args)
+            .output()
+            .unwrap()
+}
+
+#[test]
+fn rows_that_contradict_each_other_are_an_error()
+{
+    let output = wordle_solve(&["--list", "--", "-c -r -a -n -e", "~e -l -d -o -t"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("e is gray, so it isn't in the word, but it's also green or yellow."), "{}", stderr);
+
+    let output = wordle_solve(&["--list", "--", "-c -r -a -n ~e", "~e -l -d -o -t"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn precompute_needs_the_cache()
+{
+    let output = wordle_solve(&["precompute"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("nothing is cached"), "{}", stderr);
+}
+
+#[test]
+fn the_built_in_word_list_gets_the_same_warnings()
+{
+    // A new directory, so there's no words file.
+    let dir = env::temp_dir().join(format!("wordle-solve-builtin-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let output = wordle_solve_in(&dir, &["--word-length", "6", "dict-hash"]);
+    fs::remove_dir_all(&dir).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("words in the built-in word list that aren't 6 letters long."), "{}", stderr);
+}
+
+#[test]
+fn json_output_leaves_out_the_narration()
+{
+    let words = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("words")).unwrap();
+    let path = env::temp_dir().join(format!("wordle-solve-json-{}", process::id()));
+    fs::write(&path, words.lines().take(30).collect::<Vec<_>>().join("\n")).unwrap();
+    let output = wordle_solve(&["--words", path.to_str().unwrap(), "-v", "--json", "full-test"]);
+    fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 30);
+    for line in stdout.lines() {
+        assert!(serde_json::from_str::<serde_json::Value>(line).is_ok(), "{}", line);
+    }
+}
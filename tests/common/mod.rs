@@ -0,0 +1,29 @@
+//! Word lists and solvers shared by the tests.
+
+// Each test file only uses some of these.
+#![allow(dead_code)]
+
+use wordle_solve::{read_words, Scoring, Word, WordleSolver};
+
+/// The whole word list.
+pub fn dictionary() -> Vec<Word>
+{
+    let path = format!("{}/words", env!("CARGO_MANIFEST_DIR"));
+    read_words(&path, None).unwrap().words
+}
+
+/// The first `n` words of the word list, which is quick to solve. They all
+/// start with a or b.
+pub fn small_dictionary(n: usize) -> Vec<Word>
+{
+    let mut words = dictionary();
+    words.truncate(n);
+    words
+}
+
+/// A solver that scores by eliminations, with the first `n` words of the word
+/// list as both guesses and answers.
+pub fn small_solver(n: usize) -> WordleSolver
+{
+    WordleSolver::new(small_dictionary(n), None, None, Scoring::Eliminations)
+}
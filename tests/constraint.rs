@@ -0,0 +1,409 @@
+mod common;
+
+use common::dictionary;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use wordle_solve::{filter_words, wordle_guess, Constraint, RowFormat, RowOps, Scoring, Word, WordleSolver};
+
+#[test]
+fn from_string_rejects_malformed_rows()
+{
+    assert!(Constraint::from_string("-r -a ~i -s -e", 5).is_ok());
+    // Too few letters.
+    assert!(Constraint::from_string("a b c", 5).is_err());
+    // Trailing prefix without a letter.
+    assert!(Constraint::from_string("-r -a ~i -s -", 5).is_err());
+    // Prefix followed by a space.
+    assert!(Constraint::from_string("-r -a ~ i -s", 5).is_err());
+    // Two letters in one position.
+    assert!(Constraint::from_string("-r -a ~ix -s -e", 5).is_err());
+}
+
+#[test]
+fn rows_of_the_wrong_length_are_errors()
+{
+    assert_eq!(Constraint::from_string("-r -a ~i -s -e -d", 5).err().unwrap(),
+        "Row '-r -a ~i -s -e -d' has 6 letters but word length is 5.");
+    assert!(Constraint::from_string("-r -a ~i -s -e", 6).is_err());
+    assert!(Constraint::from_string("-r -a ~i -s -e ", 5).is_err());
+    assert!(Constraint::from_row("raised XYGXXX", RowFormat::Gyx, 5).is_err());
+    assert!(Constraint::from_row("raise XYGXXX", RowFormat::Gyx, 5).is_err());
+}
+
+
+/// Check that `row` allows exactly the words that `wordle_guess` does for
+/// `guess` against `answer`.
+fn assert_row_matches(row: &str, guess: &str, answer: &str)
+{
+    let from_row = Constraint::from_string(row, 5).unwrap();
+    let guess = Word::new(guess.to_string());
+    let answer = Word::new(answer.to_string());
+    let from_guess = wordle_guess(&guess, &answer);
+    assert!(from_row.allows(&answer), "'{}' doesn't allow {}", row, answer.as_str());
+    for word in dictionary() {
+        assert_eq!(from_row.allows(&word), from_guess.allows(&word),
+            "'{}' and wordle_guess disagree about {}", row, word.as_str());
+    }
+}
+
+#[test]
+fn repeated_letter_gray_and_green()
+{
+    // Only the last E is in the answer.
+    assert_row_matches("-e -e -r -i e", "eerie", "abate");
+}
+
+#[test]
+fn repeated_letter_gray_and_yellow()
+{
+    // The answer has one E, in a position the guess didn't use.
+    assert_row_matches("~e -e ~r i -e", "eerie", "their");
+}
+
+#[test]
+fn repeated_letter_green_and_yellow()
+{
+    // The answer has two E's, one of them where the guess put it.
+    assert_row_matches("~e e -r -i -e", "eerie", "beset");
+}
+
+#[test]
+fn forbidden_letters_exclude_words_anywhere()
+{
+    let words = dictionary();
+    let constraint = Constraint::from_string("!a !e", 5).unwrap();
+    let allowed = filter_words(&constraint, &words);
+    assert!(!allowed.is_empty());
+    assert!(allowed.iter().all(|w| !w.as_str().contains('a') && !w.as_str().contains('e')));
+
+    // Forbidden letters can share a row with positional ones, and merge.
+    let mut merged = Constraint::from_string("-r ~o -u -n -d !t", 5).unwrap();
+    merged.update(&Constraint::from_string("!s", 5).unwrap());
+    assert!(!merged.allows(&Word::new("stock".to_string())));
+    assert!(!merged.allows(&Word::new("cloth".to_string())));
+    assert!(merged.allows(&Word::new("block".to_string())));
+
+    assert!(Constraint::from_string("!", 5).is_err());
+    assert!(Constraint::from_string("!ab", 5).is_err());
+}
+
+#[test]
+fn gray_copies_next_to_green_ones_still_allow_the_answer()
+{
+    // A real game: each of the guesses has a green e and a gray one.
+    let elbow = Word::new("elbow".to_string());
+    let eerie = Constraint::from_string("e -e -r -i -e", 5).unwrap();
+    let elder = Constraint::from_string("e l -d -e -r", 5).unwrap();
+    assert!(eerie == wordle_guess(&Word::new("eerie".to_string()), &elbow));
+    assert!(elder == wordle_guess(&Word::new("elder".to_string()), &elbow));
+
+    for (first, second) in [(&eerie, &elder), (&elder, &eerie)] {
+        let mut merged = first.clone();
+        merged.update(second);
+        assert!(merged.allows(&elbow));
+        assert_eq!(merged.contradiction(), None);
+        // Both rows say there's exactly one e.
+        assert!(!merged.allows(&Word::new("elbee".to_string())));
+    }
+}
+
+#[test]
+fn gray_then_green_in_a_later_row_is_a_contradiction()
+{
+    // The first row says there's no e at all, the second that it starts the
+    // word. No answer fits both, so the merge is reported instead of
+    // quietly letting words through.
+    let gray = Constraint::from_string("-c -r -a -n -e", 5).unwrap();
+    let green = Constraint::from_string("e -v -e -n -t", 5).unwrap();
+    for (first, second) in [(&gray, &green), (&green, &gray)] {
+        let mut merged = first.clone();
+        merged.update(second);
+        assert_eq!(merged.contradiction().unwrap(),
+            "e is gray, so it isn't in the word, but it's also green or yellow.");
+        assert!(!merged.allows(&Word::new("elbow".to_string())));
+    }
+}
+
+#[test]
+fn display_shows_what_is_known()
+{
+    let mut constraint = Constraint::from_string("-r -a ~i -s -e", 5).unwrap();
+    constraint.update(&Constraint::from_string("-h -o ~t -l y !q", 5).unwrap());
+    assert_eq!(constraint.to_string(), "Green:    _ _ _ _ y\n\
+        Not at 1: h r\n\
+        Not at 2: a o\n\
+        Not at 3: i t\n\
+        Not at 4: l s\n\
+        At least: 1 i, 1 t, 1 y\n\
+        At most:  0 a, 0 e, 0 h, 0 l, 0 o, 0 r, 0 s\n\
+        Nowhere:  q");
+    assert_eq!(Constraint::new(5).to_string(), "Green:    _ _ _ _ _");
+}
+
+#[test]
+fn constraint_survives_a_json_round_trip()
+{
+    let mut constraint = Constraint::from_string("-r -a ~i -s -e", 5).unwrap();
+    constraint.update(&Constraint::from_string("!q", 5).unwrap());
+    let json = serde_json::to_string(&constraint).unwrap();
+    let loaded: Constraint = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.len(), 5);
+    assert_eq!(loaded.to_string(), constraint.to_string());
+}
+
+#[test]
+fn built_rows_match_parsed_rows()
+{
+    let words = dictionary();
+    let parsed = Constraint::from_string("-r -a ~i -s -e", 5).unwrap();
+    let mut built = Constraint::new(5);
+    built.add_gray('r').add_gray('a').add_yellow(2, 'i').add_gray('s').add_gray('e');
+    assert!(filter_words(&parsed, &words) == filter_words(&built, &words));
+
+    let parsed = Constraint::from_string("t h e r e", 5).unwrap();
+    let mut built = Constraint::new(5);
+    for (pos, c) in "there".chars().enumerate() {
+        built.set_green(pos, c);
+    }
+    assert_eq!(built.to_string(), parsed.to_string());
+    assert!(built.is_solved());
+
+    // A gray copy doesn't cancel out a yellow one, in either order.
+    let mut gray_first = Constraint::new(5);
+    gray_first.add_gray('e').add_yellow(1, 'e');
+    let mut yellow_first = Constraint::new(5);
+    yellow_first.add_yellow(1, 'e').add_gray('e');
+    assert_eq!(gray_first.to_string(), yellow_first.to_string());
+    assert!(!gray_first.allows(&Word::new("eject".to_string())));
+    assert!(gray_first.allows(&Word::new("outer".to_string())));
+}
+
+fn contradiction(rows: &[&str]) -> Option<String>
+{
+    let mut constraint = Constraint::new(5);
+    for row in rows {
+        constraint.update(&Constraint::from_string(row, 5).unwrap());
+    }
+    assert_eq!(constraint.is_satisfiable(), constraint.contradiction().is_none());
+    constraint.contradiction()
+}
+
+#[test]
+fn contradictions_are_explained()
+{
+    assert_eq!(contradiction(&["-r -a ~i -s -e", "-h -o ~t -l y"]), None);
+
+    // Green in a position that another row rules out.
+    assert_eq!(contradiction(&["a -x -y -z -w", "-a -b -c -d -f"]).unwrap(),
+        "letter 1 is a but also can't be a.");
+    // Green or yellow, but also nowhere in the word.
+    assert_eq!(contradiction(&["~a -x -y -z -w", "!a"]).unwrap(),
+        "a is green or yellow but also isn't anywhere in the word.");
+    // More known letters than fit in a word.
+    assert_eq!(contradiction(&["~a ~b ~c -g -h", "-x ~d ~e ~f -y"]).unwrap(),
+        "there are at least 6 known letters but words only have 5.");
+
+    // A letter that's gray in one row, but yellow or green in another.
+    assert_eq!(contradiction(&["-c -r -a -n -e", "~e -l -d -o -t"]).unwrap(),
+        "e is gray, so it isn't in the word, but it's also green or yellow.");
+    assert_eq!(contradiction(&["e -e -r -i -e", "e ~e -x -y -z"]).unwrap(),
+        "there are at least 2 copies of e but at most 1.");
+
+    // Rows can't produce this one on their own, but saved state can.
+    let mut state: serde_json::Value =
+        serde_json::to_value(Constraint::from_string("e ~e -x -y -z", 5).unwrap()).unwrap();
+    state["max_occurrence"]["e"] = 1.into();
+    state["min_occurrence"]["e"] = 1.into();
+    state["character"][1]["is"] = "e".into();
+    state["character"][1]["is_not"] = serde_json::json!([]);
+    let constraint: Constraint = serde_json::from_value(state).unwrap();
+    assert_eq!(constraint.contradiction().unwrap(), "e is green in 2 places but there are at most 1.");
+}
+
+#[test]
+fn shares_merge_every_row()
+{
+    let share = "raise\n⬛⬛🟨⬛⬛\n\nclout\n⬛⬛⬛⬛🟨\nwitty\n⬛🟩⬛🟩🟩\n";
+    let constraint = Constraint::from_share(share).unwrap();
+    let words = dictionary();
+    let remaining: Vec<&str> = filter_words(&constraint, &words).iter().map(|w| w.as_str()).collect();
+    assert_eq!(remaining, vec!["fifty", "minty"]);
+
+    // A guess without its squares.
+    assert!(Constraint::from_share("raise\n⬛⬛🟨⬛⬛\nclout\n").is_err());
+    // Guesses of different lengths.
+    assert!(Constraint::from_share("raise\n⬛⬛🟨⬛⬛\nclouts\n⬛⬛⬛⬛🟨⬛\n").is_err());
+}
+
+#[test]
+fn exact_letter_counts()
+{
+    let constraint = Constraint::from_string("=2e", 5).unwrap();
+    assert!(constraint.allows(&Word::new("beech".to_string())));
+    assert!(!constraint.allows(&Word::new("geese".to_string())));
+    assert!(!constraint.allows(&Word::new("raise".to_string())));
+
+    // Counts go with a row, and can be merged with other rows.
+    let mut constraint = Constraint::from_string("-r -a -i -s e =2e", 5).unwrap();
+    constraint.update(&Constraint::from_string("=0b", 5).unwrap());
+    let words = dictionary();
+    let remaining = filter_words(&constraint, &words);
+    assert!(!remaining.is_empty());
+    assert!(remaining.iter().all(|w| w.char_count(&'e') == 2 && w.char_count(&'b') == 0));
+    assert!(remaining.iter().any(|w| w.as_str() == "elope"));
+
+    assert!(Constraint::from_string("=e", 5).is_err());
+    assert!(Constraint::from_string("=2", 5).is_err());
+    assert!(Constraint::from_string("=2ee", 5).is_err());
+}
+
+#[test]
+fn present_letters_rule_out_no_position()
+{
+    let words = dictionary();
+    let present = filter_words(&Constraint::from_string("+e", 5).unwrap(), &words);
+    let containing: Vec<_> = words.iter().filter(|w| w.char_count(&'e') > 0).collect();
+    assert!(present == containing);
+    // A yellow e also rules out the e in the first position.
+    let yellow = Constraint::from_string("~e -x -x -x -x", 5).unwrap();
+    assert!(present.iter().any(|w| w.as_str().starts_with('e')));
+    assert!(!filter_words(&yellow, &words).iter().any(|w| w.as_str().starts_with('e')));
+
+    let twice = filter_words(&Constraint::from_string("+e +e", 5).unwrap(), &words);
+    assert!(twice.iter().all(|w| w.char_count(&'e') >= 2));
+    assert!(Constraint::from_string("-r -a -i -s -e +e", 5).unwrap().contradiction().is_some());
+
+    assert!(Constraint::from_string("+", 5).is_err());
+    assert!(Constraint::from_string("+ee", 5).is_err());
+}
+
+#[test]
+fn solver_counts_the_remaining_dictionary()
+{
+    let solver = WordleSolver::new(dictionary(), None, None, Scoring::Eliminations);
+    let constraint = Constraint::from_string("-r -a ~i -s -e", 5).unwrap();
+    let remaining = solver.remaining_words(&constraint);
+    assert!(remaining == filter_words(&constraint, solver.answers()));
+    assert_eq!(solver.remaining_count(&constraint), remaining.len());
+    assert_eq!(solver.remaining_count(&Constraint::new(5)), solver.answers().len());
+}
+
+fn hash(constraint: &Constraint) -> u64
+{
+    let mut hasher = DefaultHasher::new();
+    constraint.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn equal_constraints_hash_the_same()
+{
+    // Built in different orders, so their sets and maps differ inside.
+    let mut a = Constraint::from_string("-r -a ~i -s -e", 5).unwrap();
+    a.update(&Constraint::from_string("-h -o ~t -l -y", 5).unwrap());
+    let mut b = Constraint::from_string("-h -o ~t -l -y", 5).unwrap();
+    b.update(&Constraint::from_string("-r -a ~i -s -e", 5).unwrap());
+    assert!(a == b);
+    assert_eq!(hash(&a), hash(&b));
+    assert!(a != Constraint::from_string("-r -a ~i -s -e", 5).unwrap());
+}
+
+#[test]
+fn remaining_words_stay_right_as_the_cache_fills()
+{
+    let words = dictionary();
+    let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations);
+    // More constraints than the cache holds, each asked about twice.
+    let constraints: Vec<Constraint> = words.iter().take(5)
+            .flat_map(|guess| words.iter().skip(50).take(10).map(|answer| wordle_guess(guess, answer)))
+            .collect();
+    for _ in 0..2 {
+        for constraint in &constraints {
+            assert!(solver.remaining_words(constraint) == filter_words(constraint, &words));
+        }
+    }
+}
+
+#[test]
+fn row_formats_agree()
+{
+    let words = dictionary();
+    let prefix = Constraint::from_row("-r -a ~i -s e", RowFormat::Prefix, 5).unwrap();
+    let gyx = Constraint::from_row("raise XXYXG", RowFormat::Gyx, 5).unwrap();
+    let emoji = Constraint::from_row("raise ⬛⬛🟨⬛🟩", RowFormat::Emoji, 5).unwrap();
+    let expected = filter_words(&prefix, &words);
+    assert!(filter_words(&gyx, &words) == expected);
+    assert!(filter_words(&emoji, &words) == expected);
+    assert!(filter_words(&Constraint::from_row("raise xxyxg", RowFormat::Gyx, 5).unwrap(), &words) == expected);
+
+    // No result, the wrong letters, or the wrong length.
+    assert!(Constraint::from_row("raise", RowFormat::Gyx, 5).is_err());
+    assert!(Constraint::from_row("raise XXBXG", RowFormat::Gyx, 5).is_err());
+    assert!(Constraint::from_row("raise XXYX", RowFormat::Gyx, 5).is_err());
+    assert!(Constraint::from_row("raises XXYXGX", RowFormat::Gyx, 5).is_err());
+}
+
+#[test]
+fn custom_markers()
+{
+    let words = dictionary();
+    let expected = filter_words(&Constraint::from_string("-r -a ~i -s e", 5).unwrap(), &words);
+    let ops: RowOps = "X Y .".parse().unwrap();
+    let constraint = Constraint::from_string_with_ops("Xr Xa Yi Xs .e", 5, &ops).unwrap();
+    assert!(filter_words(&constraint, &words) == expected);
+    // Green letters can still be bare, and ! still works.
+    let constraint = Constraint::from_string_with_ops("Xr Xa Yi Xs e !s", 5, &ops).unwrap();
+    assert!(filter_words(&constraint, &words) == expected);
+    // The default markers no longer mean anything.
+    assert!(Constraint::from_string_with_ops("-r -a ~i -s e", 5, &ops).is_err());
+    assert!(Constraint::from_string_with_ops("X a b c d", 5, &ops).is_err());
+
+    assert_eq!("X Y".parse::<RowOps>().unwrap(), RowOps { gray: 'X', yellow: 'Y', green: None });
+    assert!("X".parse::<RowOps>().is_err());
+    assert!("X X".parse::<RowOps>().is_err());
+    assert!("X Y . Z".parse::<RowOps>().is_err());
+    assert!("XX Y".parse::<RowOps>().is_err());
+    assert!("! Y".parse::<RowOps>().is_err());
+}
+
+#[test]
+fn given_letters_are_green_from_the_start()
+{
+    let constraint = Constraint::from_given("b _ _ e _", 5).unwrap();
+    assert!(constraint == *Constraint::new(5).set_green(0, 'b').set_green(3, 'e'));
+    assert!(Constraint::from_given("b__e_", 5).unwrap() == constraint);
+    assert!(Constraint::from_given("b _ e _", 5).is_err());
+    assert!(Constraint::from_given("b _ ? e _", 5).is_err());
+
+    let solver = WordleSolver::new(dictionary(), None, None, Scoring::Eliminations).with_hard_mode(true);
+    let remaining = solver.remaining_words(&constraint);
+    assert!(!remaining.is_empty() && remaining.len() < solver.answers().len());
+    assert!(remaining.iter().all(|word| word.as_str().starts_with('b') && word.as_str()[3..].starts_with('e')));
+    let guess = solver.best_guess(&constraint, false).unwrap().as_str();
+    assert!(guess.starts_with('b') && guess[3..].starts_with('e'), "{}", guess);
+}
+
+#[test]
+fn checked_merges_agree_with_every_row()
+{
+    let words = dictionary();
+    let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations).with_strict(true);
+    // Rows wordle could really show, including ones with repeated letters.
+    for answer in words.iter().step_by(97) {
+        let mut checked = Constraint::new(5);
+        let mut merged = Constraint::new(5);
+        for guess in words.iter().step_by(131).take(6) {
+            let row = wordle_guess(guess, answer);
+            solver.merge(&mut checked, &row).unwrap();
+            merged.update(&row);
+            assert!(checked == merged);
+        }
+    }
+
+    // A gray e that's yellow later can't both be true, so the merge is an
+    // error, and the constraint is left alone.
+    let mut constraint = Constraint::from_string("-c -r -a -n -e", 5).unwrap();
+    let before = constraint.clone();
+    assert!(solver.merge(&mut constraint, &Constraint::from_string("~e -l -d -o -t", 5).unwrap()).is_err());
+    assert!(constraint == before);
+}
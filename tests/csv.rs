@@ -0,0 +1,25 @@
+mod common;
+
+use common::small_solver;
+
+#[test]
+fn full_test_csv_has_a_row_per_answer()
+{
+    let solver = small_solver(100);
+
+    let mut csv = Vec::new();
+    let summary = solver.full_test_csv(false, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "word,guesses,sequence");
+    assert_eq!(lines.len(), summary.len() + 1);
+    // Rows are written as the games end, in no particular order.
+    lines[1..].sort();
+    for (line, answer) in lines[1..].iter().zip(solver.answers()) {
+        let fields: Vec<&str> = line.split(',').collect();
+        assert_eq!(fields[0], answer.as_str());
+        let sequence: Vec<&str> = fields[2].split('|').collect();
+        assert_eq!(fields[1], sequence.len().to_string());
+        assert_eq!(*sequence.last().unwrap(), answer.as_str());
+    }
+}
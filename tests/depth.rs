@@ -0,0 +1,24 @@
+mod common;
+
+use common::small_solver;
+
+#[test]
+fn looking_ahead_takes_fewer_guesses()
+{
+    let one_ply = small_solver(150).with_feedback_matrix(true).with_depth(1).full_test(false);
+    let two_ply = small_solver(150).with_feedback_matrix(true).with_depth(2).full_test(false);
+    assert_eq!(two_ply.failures(), 0);
+    assert!(two_ply.mean() < one_ply.mean(), "{} vs {}", two_ply.mean(), one_ply.mean());
+}
+
+#[test]
+fn looking_ahead_without_the_matrix_plays_the_same_games()
+{
+    // Without the matrix, elimination scores are pruned unless the solver is
+    // going to look ahead, so both have to pick from the same runners-up.
+    let matrix = small_solver(150).with_feedback_matrix(true).with_depth(2);
+    let pruned = small_solver(150).with_depth(2);
+    for answer in matrix.answers() {
+        assert!(pruned.test(answer, false) == matrix.test(answer, false), "{}", answer.as_str());
+    }
+}
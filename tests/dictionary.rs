@@ -0,0 +1,78 @@
+use wordle_solve::{check_words, check_words_from_str, read_words, read_words_from_str, Constraint, Scoring, Word, WordleSolver};
+
+fn read(name: &str) -> Vec<Word>
+{
+    let path = format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name);
+    read_words(&path, None).unwrap().words
+}
+
+#[test]
+fn empty_dictionary_has_no_guesses()
+{
+    let words = read("empty");
+    assert!(words.is_empty());
+    let solver = WordleSolver::new(words, None, None, Scoring::Eliminations);
+    assert!(solver.best_guess(&Constraint::new(5), false).is_err());
+    assert_eq!(solver.full_test(false).len(), 0);
+}
+
+#[test]
+fn one_word_dictionary_guesses_it_right_away()
+{
+    let words = read("one_word");
+    assert_eq!(words.len(), 1);
+    for matrix in [false, true] {
+        let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations)
+                .with_feedback_matrix(matrix);
+        assert_eq!(solver.best_guess(&Constraint::new(5), false).unwrap().as_str(), "raise");
+        let result = solver.test(&words[0], false);
+        assert!(result.is_solved());
+        assert_eq!(result.guesses().len(), 1);
+        assert_eq!(solver.full_test(false).mean(), 1.0);
+    }
+}
+
+#[test]
+fn words_from_a_string_hash_like_the_file()
+{
+    let path = format!("{}/words", env!("CARGO_MANIFEST_DIR"));
+    let file = read_words(&path, None).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let string = read_words_from_str(&text, None).unwrap();
+    assert_eq!(string.hash, file.hash);
+    assert!(string.words == file.words);
+
+    assert_eq!(read_words_from_str("raise\nhotly\n\nfifty", None).unwrap().words.len(), 3);
+    assert!(read_words_from_str("raise\nhot", None).is_err());
+    assert_eq!(read_words_from_str("raise\nhot", Some(5)).unwrap().skipped, 1);
+}
+
+#[test]
+fn duplicate_words_are_left_out()
+{
+    let list = read_words_from_str("raise\nhotly\nraise\nfifty\nhotly", None).unwrap();
+    let words: Vec<&str> = list.words.iter().map(Word::as_str).collect();
+    assert_eq!(words, ["raise", "hotly", "fifty"]);
+    assert_eq!(list.duplicates, 2);
+    // The hash is of the file as it is, duplicates and all.
+    assert_ne!(list.hash, read_words_from_str("raise\nhotly\nfifty", None).unwrap().hash);
+}
+
+#[test]
+fn check_reports_every_problem()
+{
+    let check = check_words_from_str("raise\nhot\n\nraise\ndon't\n").unwrap();
+    assert_eq!(check.words, 4);
+    assert_eq!(check.lengths.into_iter().collect::<Vec<_>>(), [(3, 1), (5, 3)]);
+    assert_eq!(check.duplicates, 1);
+    assert_eq!(check.non_alphabetic, 1);
+    assert_eq!(check.first_non_alphabetic.as_deref(), Some("don't"));
+
+    let path = format!("{}/words", env!("CARGO_MANIFEST_DIR"));
+    let check = check_words(&path).unwrap();
+    assert!(check.is_ok());
+    assert_eq!(check.words, read_words(&path, None).unwrap().words.len());
+    assert_eq!(check.duplicates + check.non_alphabetic, 0);
+    assert!(!check_words_from_str("").unwrap().is_ok());
+    assert!(!check_words_from_str("raise\nhot").unwrap().is_ok());
+}
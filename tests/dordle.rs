@@ -0,0 +1,43 @@
+mod common;
+
+use common::small_solver;
+use wordle_solve::{wordle_guess, Constraint, Word, WordleSolver};
+
+fn find<'a>(solver: &'a WordleSolver, word: &str) -> &'a Word
+{
+    solver.answers().iter().find(|w| w.as_str() == word).unwrap()
+}
+
+#[test]
+fn dordle_solves_both_boards()
+{
+    let solver = small_solver(100);
+    let answers = [find(&solver, "adobe"), find(&solver, "alien")];
+    let guesses = solver.test_dordle(&answers, false).unwrap();
+    for answer in answers {
+        assert!(guesses.contains(&answer), "{} was never guessed", answer.as_str());
+    }
+    assert!(answers.contains(guesses.last().unwrap()));
+}
+
+#[test]
+fn dordle_gives_up_after_max_guesses()
+{
+    let solver = small_solver(100).with_max_guesses(1);
+    let answers = [find(&solver, "adobe"), find(&solver, "alien")];
+    assert!(solver.test_dordle(&answers, false).is_err());
+}
+
+#[test]
+fn no_guess_once_every_board_is_solved()
+{
+    let solver = small_solver(100);
+    let boards: Vec<Constraint> = ["adobe", "alien"].iter()
+            .map(|answer| {
+                let answer = find(&solver, answer);
+                wordle_guess(answer, answer)
+            })
+            .collect();
+    assert!(boards.iter().all(|board| board.is_solved()));
+    assert!(solver.best_dordle_guess(&boards).is_err());
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::small_solver;
+use wordle_solve::{wordle_guess, Constraint};
+
+#[test]
+fn endgame_only_guesses_candidates()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    let endgame = small_solver(150).with_feedback_matrix(true).with_endgame_threshold(10);
+    let mut restricted = 0;
+    for answer in solver.answers() {
+        let mut constraint = Constraint::new(5);
+        for guess in solver.test(answer, false).guesses() {
+            let endgame_guess = endgame.best_guess(&constraint, false).unwrap();
+            if solver.remaining_count(&constraint) <= 10 {
+                assert!(constraint.allows(endgame_guess), "{} for {}", endgame_guess.as_str(), answer.as_str());
+                if !constraint.allows(guess) {
+                    restricted += 1;
+                }
+            } else {
+                // Early in the game, guesses that can't be the answer are
+                // still fair.
+                assert!(endgame_guess == *guess);
+            }
+            constraint.update(&wordle_guess(guess, answer));
+        }
+    }
+    // Without a threshold, the solver does sometimes guess a word it knows
+    // isn't the answer.
+    assert!(restricted > 0);
+}
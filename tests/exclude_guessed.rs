@@ -0,0 +1,29 @@
+mod common;
+
+use common::small_solver;
+use wordle_solve::Constraint;
+
+#[test]
+fn guessed_words_are_not_suggested_again()
+{
+    let constraint = Constraint::new(5);
+    let solver = small_solver(100).with_exclude_guessed(true);
+    let first = solver.best_guess(&constraint, false).unwrap();
+    let second = solver.best_guess_excluding(&constraint, &[first], false).unwrap();
+    assert!(first != second);
+
+    // Without the option, the guessed words are ignored.
+    let solver = small_solver(100);
+    let first = solver.best_guess(&constraint, false).unwrap();
+    assert!(solver.best_guess_excluding(&constraint, &[first], false).unwrap() == first);
+}
+
+#[test]
+fn excluding_everything_falls_back_to_a_candidate()
+{
+    let solver = small_solver(100).with_exclude_guessed(true);
+    let constraint = Constraint::from_string("a -b -c -d -e", 5).unwrap();
+    let guessed: Vec<_> = solver.guesses().iter().collect();
+    let guess = solver.best_guess_excluding(&constraint, &guessed, false).unwrap();
+    assert!(constraint.allows(guess));
+}
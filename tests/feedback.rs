@@ -0,0 +1,75 @@
+use wordle_solve::{feedback_pattern, wordle_guess, Scoring, Word, WordleSolver};
+
+fn pattern(guess: &str, answer: &str) -> String
+{
+    feedback_pattern(&Word::new(guess.to_string()), &Word::new(answer.to_string()))
+}
+
+#[test]
+fn feedback_pattern_handles_duplicate_letters()
+{
+    assert_eq!(pattern("alloy", "loyal"), "YYYYY");
+    assert_eq!(pattern("loyal", "alloy"), "YYYYY");
+    assert_eq!(pattern("speed", "abide"), "XXYXY");
+    assert_eq!(pattern("geese", "these"), "XXGGG");
+    assert_eq!(pattern("eerie", "their"), "YXYGX");
+    assert_eq!(pattern("loyal", "loyal"), "GGGGG");
+}
+
+#[test]
+fn wordle_guess_agrees_with_feedback_pattern()
+{
+    let alloy = Word::new("alloy".to_string());
+    let loyal = Word::new("loyal".to_string());
+    let constraint = wordle_guess(&alloy, &loyal);
+    assert!(constraint.allows(&loyal));
+    assert!(!constraint.allows(&alloy));
+    assert!(!constraint.is_solved());
+    assert!(wordle_guess(&loyal, &loyal).is_solved());
+}
+
+#[test]
+fn char_count_matches_for_ascii_and_unicode_words()
+{
+    let alloy = Word::new("alloy".to_string());
+    assert_eq!(alloy.char_count(&'l'), 2);
+    assert_eq!(alloy.char_count(&'z'), 0);
+    assert_eq!(alloy.char_count(&'ñ'), 0);
+
+    let anejo = Word::new("añejo".to_string());
+    assert_eq!(anejo.char_count(&'ñ'), 1);
+    assert_eq!(anejo.char_count(&'a'), 1);
+    assert_eq!(pattern("añejo", "jaleo"), "YXYYG");
+}
+
+#[test]
+fn partition_groups_by_feedback_pattern()
+{
+    let words: Vec<Word> = ["loyal", "alloy", "llama", "royal", "speed"].iter()
+            .map(|w| Word::new(w.to_string()))
+            .collect();
+    let candidates: Vec<&Word> = words.iter().collect();
+    let buckets = WordleSolver::partition(&words[0], &candidates);
+    assert_eq!(buckets.values().map(Vec::len).sum::<usize>(), words.len());
+    assert_eq!(buckets["GGGGG"].len(), 1);
+    assert!(buckets["YYYYY"][0] == &words[1]);
+    assert_eq!(buckets["XXXXX"].len(), 1);
+    for (pattern, bucket) in &buckets {
+        assert!(bucket.iter().all(|w| feedback_pattern(&words[0], w) == *pattern));
+    }
+}
+
+#[test]
+fn winning_guesses_split_every_candidate_apart()
+{
+    let words: Vec<Word> = ["films", "light", "might", "night", "sight"].iter()
+            .map(|w| Word::new(w.to_string()))
+            .collect();
+    let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations);
+    let ight: Vec<&Word> = words[1..].iter().collect();
+    // Only films tells l, m, n and s apart.
+    assert!(solver.winning_guesses(&ight) == [&words[0]]);
+    // The candidates come first, since they might be the answer.
+    let candidates = [&words[1], &words[3]];
+    assert!(solver.winning_guesses(&candidates) == [&words[1], &words[3], &words[0]]);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::small_dictionary;
+use std::collections::HashMap;
+use wordle_solve::{Constraint, Scoring, Word, WordleSolver};
+
+fn words() -> Vec<Word>
+{
+    small_dictionary(150)
+}
+
+fn scores(solver: &WordleSolver, constraint: &Constraint) -> HashMap<String, f64>
+{
+    solver.ranked_guesses(constraint, usize::MAX).unwrap()
+            .into_iter()
+            .map(|(guess, score)| (guess.as_str().to_string(), score))
+            .collect()
+}
+
+/// The same words, with uneven weights.
+fn weighted_words() -> Vec<Word>
+{
+    words().into_iter()
+            .enumerate()
+            .map(|(i, word)| word.with_weight((i % 7) as f64 * 0.5))
+            .collect()
+}
+
+#[test]
+fn matrix_scores_match_wordle_guess()
+{
+    check_matrix_scores(words);
+}
+
+#[test]
+fn weighted_matrix_scores_match_wordle_guess()
+{
+    check_matrix_scores(weighted_words);
+}
+
+fn check_matrix_scores(words: fn() -> Vec<Word>)
+{
+    let rows = ["", "-r ~a -i -s -e"];
+    for scoring in [Scoring::Eliminations, Scoring::Entropy, Scoring::Minimax, Scoring::Expected] {
+        let plain = WordleSolver::new(words(), None, None, scoring);
+        let matrix = WordleSolver::new(words(), None, None, scoring).with_feedback_matrix(true);
+        for row in rows {
+            let constraint = if row.is_empty() {
+                Constraint::new(5)
+            } else {
+                Constraint::from_string(row, 5).unwrap()
+            };
+            let expected = scores(&plain, &constraint);
+            let actual = scores(&matrix, &constraint);
+            assert_eq!(expected.len(), actual.len());
+            for (guess, score) in expected {
+                assert!((actual[&guess] - score).abs() < 1e-9,
+                    "{:?} scores {} as {} instead of {} after '{}'", scoring, guess, actual[&guess], score, row);
+            }
+        }
+    }
+}
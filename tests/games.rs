@@ -0,0 +1,151 @@
+mod common;
+
+use common::{small_dictionary, small_solver};
+use std::sync::Mutex;
+use wordle_solve::{feedback_pattern, wordle_guess, Constraint, Scoring, Verbosity, Word, WordleSolver};
+
+/// Play a game one `best_guess` at a time, filtering the whole word list
+/// for every guess.
+fn play<'a>(solver: &'a WordleSolver, answer: &Word) -> Vec<&'a Word>
+{
+    let mut constraint = Constraint::new(5);
+    let mut guesses = Vec::new();
+    loop {
+        let guess = solver.best_guess(&constraint, false).unwrap();
+        guesses.push(guess);
+        if guess == answer {
+            return guesses;
+        }
+        constraint.update(&wordle_guess(guess, answer));
+    }
+}
+
+#[test]
+fn test_plays_the_same_guesses_as_best_guess()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    for answer in solver.answers() {
+        let result = solver.test(answer, false);
+        assert!(result.is_solved());
+        assert!(*result.guesses() == play(&solver, answer), "{}", answer.as_str());
+    }
+}
+
+#[test]
+fn test_answers_only_plays_the_given_games()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    let answers: Vec<Word> = solver.answers().iter().step_by(10).cloned().collect();
+    let summary = solver.test_answers(&answers, false, None).unwrap();
+    assert_eq!(summary.len(), answers.len());
+    let total: usize = answers.iter().map(|answer| solver.test(answer, false).guesses().len()).sum();
+    assert!((summary.mean() - total as f64 / answers.len() as f64).abs() < 1e-9);
+
+    assert!(solver.test_answers(&[Word::new("zzzzz".to_string())], false, None).is_err());
+}
+
+#[test]
+fn test_answers_with_reports_every_game()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    let games = Mutex::new(Vec::new());
+    let summary = solver.test_answers_with(solver.answers(), false, None, &|answer, result| {
+        games.lock().unwrap().push((answer.as_str().to_string(), result.guesses().len()));
+    }).unwrap();
+    let mut games = games.into_inner().unwrap();
+    games.sort();
+    assert_eq!(games.len(), 150);
+    for ((answer, guesses), word) in games.iter().zip(solver.answers()) {
+        assert_eq!(answer, word.as_str());
+        assert_eq!(*guesses, solver.test(word, false).guesses().len());
+    }
+    let total: usize = games.iter().map(|(_, guesses)| guesses).sum();
+    assert!((summary.mean() - total as f64 / 150.0).abs() < 1e-9);
+}
+
+#[test]
+fn verify_checks_every_guess()
+{
+    let solver = small_solver(150);
+    let game = solver.test(&solver.answers()[100], false);
+    let path: Vec<&str> = game.guesses().iter().map(|g| g.as_str()).collect();
+    assert!(solver.verify(&path, false).unwrap() == *game.guesses());
+
+    assert!(solver.verify(&[], false).is_err());
+    assert!(solver.verify(&["aback", "qqqqq", "apron"], false).is_err());
+    assert!(solver.verify(&["aback", "apron", "apron"], false).is_err());
+    assert!(solver.verify(&["aback", "adapt"], false).is_ok());
+    // Hard mode has to keep the a that aback put in place.
+    let solver = solver.with_hard_mode(true);
+    assert!(solver.verify(&["aback", "apron", "adapt"], false).is_err());
+}
+
+#[test]
+fn game_plays_a_turn_at_a_time()
+{
+    let solver = small_solver(150);
+    let answer = &solver.answers()[42];
+
+    // Told the feedback patterns, a game makes the same guesses as `test`.
+    let mut game = solver.new_game();
+    while !game.is_solved() {
+        let guess = game.suggest().unwrap();
+        game.observe(guess, feedback_pattern(guess, answer).as_str()).unwrap();
+        assert!(game.remaining().contains(&answer));
+    }
+    assert!(game.guesses() == solver.test(answer, false).guesses().as_slice());
+    assert!(game.total_suggest_time() >= game.suggest_time());
+
+    let mut game = solver.new_game();
+    let guess = game.suggest().unwrap();
+    assert!(game.observe(guess, "GGG").is_err());
+    assert!(game.observe(guess, "GGGGQ").is_err());
+    assert!(game.guesses().is_empty());
+}
+
+#[test]
+fn zero_weight_words_are_guessed_but_never_the_answer()
+{
+    let words: Vec<Word> = small_dictionary(150).into_iter()
+            .enumerate()
+            .map(|(i, word)| word.with_weight((i % 2) as f64))
+            .collect();
+    let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations).with_feedback_matrix(true);
+    assert_eq!(solver.answers().len(), 75);
+    assert!(solver.answers().iter().all(|answer| answer.weight() > 0.0));
+    assert_eq!(solver.guesses().len(), 150);
+
+    for guess in &words {
+        for answer in solver.answers() {
+            let constraint = wordle_guess(guess, answer);
+            if solver.remaining_count(&constraint) <= 2 {
+                assert!(solver.best_guess(&constraint, false).unwrap().weight() > 0.0);
+            }
+        }
+    }
+    let summary = solver.full_test(false);
+    assert_eq!(summary.len(), 75);
+    assert_eq!(summary.unsolved(), 0);
+}
+
+#[test]
+fn test_gives_up_when_no_word_fits()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    let result = solver.test(&Word::new("zzzzz".to_string()), false);
+    assert!(!result.is_solved());
+    assert!(!result.guesses().is_empty());
+    let result = solver.test(&Word::new("zzzz".to_string()), false);
+    assert!(!result.is_solved());
+    assert!(result.guesses().is_empty());
+}
+
+#[test]
+fn verbosity_counts_up_like_v_flags()
+{
+    let levels: Vec<Verbosity> = (0..5).map(Verbosity::from).collect();
+    assert_eq!(levels, [Verbosity::Quiet, Verbosity::Status, Verbosity::Candidates, Verbosity::Scores,
+        Verbosity::Scores]);
+    assert!(levels.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(Verbosity::default(), Verbosity::Quiet);
+}
@@ -0,0 +1,57 @@
+use wordle_solve::{filter_words, read_words, wordle_guess, Constraint, Scoring, Word, WordleSolver};
+
+fn words(name: &str) -> Vec<Word>
+{
+    let path = format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name);
+    read_words(&path, None).unwrap().words
+}
+
+/// Solve every word in the dictionary, with and without the feedback matrix,
+/// and check that the rows a game shows lead to the answer.
+fn solve_everything(name: &str, length: usize)
+{
+    let words = words(name);
+    assert!(words.iter().all(|w| w.len() == length));
+    for matrix in [false, true] {
+        let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations)
+                .with_feedback_matrix(matrix);
+        let summary = solver.full_test(false);
+        assert_eq!(summary.len(), words.len());
+        assert_eq!(summary.failures(), 0);
+
+        for answer in solver.answers() {
+            let result = solver.test(answer, false);
+            assert!(result.is_solved(), "{}", answer.as_str());
+            let mut constraint = Constraint::new(length);
+            for guess in result.guesses() {
+                constraint.update(&wordle_guess(guess, answer));
+                assert!(constraint.allows(answer));
+            }
+            assert!(constraint.is_solved());
+        }
+    }
+}
+
+#[test]
+fn four_letter_words()
+{
+    solve_everything("four", 4);
+
+    let words = words("four");
+    let constraint = Constraint::from_string("-b a l l", 4).unwrap();
+    let remaining: Vec<&str> = filter_words(&constraint, &words).iter().map(|w| w.as_str()).collect();
+    assert_eq!(remaining, vec!["call"]);
+    assert!(Constraint::from_string("-b a l l -s", 4).is_err());
+}
+
+#[test]
+fn six_letter_words()
+{
+    solve_everything("six", 6);
+
+    let words = words("six");
+    let constraint = Constraint::from_string("-a -c t -i -o -n", 6).unwrap();
+    let remaining: Vec<&str> = filter_words(&constraint, &words).iter().map(|w| w.as_str()).collect();
+    assert_eq!(remaining, vec!["better"]);
+    assert!(Constraint::from_string("-a -c t -i -o", 6).is_err());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::small_solver;
+use wordle_solve::{Constraint, GameResult};
+
+#[test]
+fn test_gives_up_after_max_guesses()
+{
+    let solver = small_solver(100).with_max_guesses(1);
+    let first = solver.best_guess(&Constraint::new(5), false).unwrap();
+    assert!(solver.test(first, false).is_solved());
+
+    let other = solver.answers().iter().find(|w| *w != first).unwrap();
+    match solver.test(other, false) {
+        GameResult::Failed(guesses) => assert!(guesses == vec![first]),
+        GameResult::Solved(_) => panic!("{} was solved in one guess", other.as_str())
+    }
+
+    let summary = solver.full_test(false);
+    assert_eq!(summary.len(), 100);
+    assert_eq!(summary.unsolved(), 99);
+    assert_eq!(summary.failures(), 99);
+
+    // Every failed game is listed with its guesses.
+    let failed = summary.failed_games();
+    assert_eq!(failed.len(), 99);
+    assert!(failed.iter().all(|(answer, guesses)| answer != first.as_str() && *guesses == vec![first.as_str()]));
+    assert!(summary.to_string().contains(&format!("FAILURES\n{}: {}", failed[0].0, first.as_str())));
+}
+
+#[test]
+fn absurdle_gives_up_after_max_guesses()
+{
+    let solver = small_solver(100);
+    let guesses = solver.absurdle(false).unwrap();
+    assert!(guesses.len() > 1);
+
+    let solver = small_solver(100).with_max_guesses(guesses.len() - 1);
+    assert!(solver.absurdle(false).is_err());
+    let solver = small_solver(100).with_max_guesses(guesses.len());
+    assert!(solver.absurdle(false).unwrap() == guesses);
+}
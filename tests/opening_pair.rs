@@ -0,0 +1,34 @@
+mod common;
+
+use common::small_solver;
+
+#[test]
+fn second_guess_ignores_the_feedback()
+{
+    let solver = small_solver(150).with_opening_pair(true);
+    let summary = solver.full_test(false);
+    assert_eq!(summary.failures(), 0);
+
+    let second = &solver.guesses()[solver.second_guess().unwrap()];
+    let followed = solver.answers().iter()
+            .filter(|answer| {
+                let guesses = solver.test(answer, false).guesses().clone();
+                guesses.len() > 2 && guesses[1] == second
+            })
+            .count();
+    // Most games go on past the second guess, and nearly all of them play it.
+    assert!(followed > solver.answers().len() / 2);
+}
+
+#[test]
+fn opening_tree_plays_the_same_guesses()
+{
+    let solver = small_solver(150).with_opening_pair(true);
+    solver.full_test(false);
+    let tree = solver.opening_tree().unwrap();
+
+    let cached = small_solver(150).with_opening_pair(true).with_decision_tree(Some(tree));
+    for answer in solver.answers() {
+        assert!(cached.test(answer, false) == solver.test(answer, false), "{}", answer.as_str());
+    }
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "plot")]
+
+use std::env;
+use std::fs;
+use std::process;
+use wordle_solve::{plot_histogram, Summary};
+
+#[test]
+fn plot_draws_a_bar_for_every_guess_count()
+{
+    let path = env::temp_dir().join(format!("wordle-solve-plot-{}.svg", process::id()));
+    let path = path.to_str().unwrap();
+    let mut summary = Summary::new();
+    for count in [3, 1, 3, 4, 3] {
+        summary.add(count);
+    }
+    plot_histogram(&summary, path).unwrap();
+    let svg = fs::read_to_string(path).unwrap();
+    // The background, then one bar for each of 1 to 4 guesses.
+    assert_eq!(svg.matches("<rect").count(), 5);
+    assert!(svg.contains("5 words, mean 2.800 guesses"));
+
+    let mut summary = Summary::new();
+    summary.add(1);
+    plot_histogram(&summary, path).unwrap();
+    plot_histogram(&Summary::new(), path).unwrap();
+    fs::remove_file(path).unwrap();
+}
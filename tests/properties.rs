@@ -0,0 +1,65 @@
+use proptest::prelude::*;
+use wordle_solve::{filter_words, wordle_guess, Constraint, Word};
+
+/// Random five letter words. Half of them only use a few letters, so
+/// repeated letters, which are the tricky part, come up a lot.
+fn word() -> impl Strategy<Value = String>
+{
+    prop_oneof!["[a-z]{5}", "[a-d]{5}"]
+}
+
+proptest! {
+    #[test]
+    fn guessing_the_answer_only_allows_the_answer(answer in word(), other in word())
+    {
+        let (answer, other) = (Word::new(answer), Word::new(other));
+        let constraint = wordle_guess(&answer, &answer);
+        prop_assert!(constraint.allows(&answer));
+        prop_assert_eq!(constraint.allows(&other), other == answer);
+    }
+
+    #[test]
+    fn feedback_allows_the_answer(guess in word(), answer in word())
+    {
+        let answer = Word::new(answer);
+        prop_assert!(wordle_guess(&Word::new(guess), &answer).allows(&answer));
+    }
+
+    #[test]
+    fn guesses_never_eliminate_the_answer(answer in word(),
+            guesses in prop::collection::vec(word(), 1..6),
+            others in prop::collection::vec(word(), 0..20))
+    {
+        let answer = Word::new(answer);
+        let mut words: Vec<Word> = others.into_iter().map(Word::new).collect();
+        words.push(answer.clone());
+        let mut constraint = Constraint::new(5);
+        for guess in guesses {
+            constraint.update(&wordle_guess(&Word::new(guess), &answer));
+            prop_assert!(filter_words(&constraint, &words).contains(&&answer));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn allows_fast_agrees_with_allows(answer in word(),
+            guesses in prop::collection::vec(word(), 0..4),
+            forbidden in prop::option::of("[a-z]"),
+            words in prop::collection::vec(word(), 1..50))
+    {
+        let answer = Word::new(answer);
+        let mut constraint = Constraint::new(5);
+        for guess in guesses {
+            constraint.update(&wordle_guess(&Word::new(guess), &answer));
+        }
+        if let Some(letter) = forbidden {
+            constraint.update(&Constraint::from_string(&format!("!{}", letter), 5).unwrap());
+        }
+        let compact = constraint.compact().unwrap();
+        for word in words.into_iter().chain([answer.as_str().to_string()]) {
+            let word = Word::new(word);
+            prop_assert_eq!(compact.allows_fast(&word), constraint.allows(&word), "{}", word.as_str());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::{small_dictionary, small_solver};
+use wordle_solve::{Constraint, Scoring, WordleSolver};
+
+#[test]
+fn pruned_best_guess_matches_exhaustive_scores()
+{
+    let solver = small_solver(150);
+    for row in ["", "-r ~a -i -s -e", "a -b -c -d -e", "a -l -o -n -e", "a -d -u -l -t"] {
+        let constraint = if row.is_empty() {
+            Constraint::new(5)
+        } else {
+            Constraint::from_string(row, 5).unwrap()
+        };
+        // `best_guess` prunes hopeless guesses, `ranked_guesses` scores all
+        // of them.
+        let best = solver.best_guess(&constraint, false).unwrap();
+        let ranked = solver.ranked_guesses(&constraint, usize::MAX).unwrap();
+        let top_score = ranked[0].1;
+        let best_score = ranked.iter().find(|(guess, _)| *guess == best).unwrap().1;
+        assert_eq!(best_score, top_score, "{} after '{}'", best.as_str(), row);
+        // Of the guesses with the top score, the alphabetically last wins.
+        let last_top = ranked.iter()
+                .take_while(|(_, score)| *score == top_score)
+                .map(|(guess, _)| *guess)
+                .max()
+                .unwrap();
+        assert!(best == last_top, "{} after '{}'", best.as_str(), row);
+    }
+}
+
+#[test]
+fn worst_guesses_are_ranked_guesses_reversed()
+{
+    let words = small_dictionary(150);
+    let solver = WordleSolver::new(words, None, None, Scoring::Minimax);
+    let constraint = Constraint::new(5);
+    let worst = solver.worst_guesses(&constraint, usize::MAX).unwrap();
+    assert_eq!(worst.len(), solver.guesses().len());
+    let ranked = solver.ranked_guesses(&constraint, usize::MAX).unwrap();
+    assert_eq!(worst[0].1, ranked.last().unwrap().1);
+    // Minimax scores tie a lot; ties are in alphabetical order.
+    assert!(worst.windows(2).any(|pair| pair[0].1 == pair[1].1));
+    for pair in worst.windows(2) {
+        assert!(pair[0].1 < pair[1].1 || (pair[0].1 == pair[1].1 && pair[0].0 < pair[1].0));
+    }
+}
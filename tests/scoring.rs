@@ -0,0 +1,142 @@
+mod common;
+
+use common::{small_dictionary, small_solver};
+use wordle_solve::{wordle_guess, Constraint, OpenerStrategy, Scoring, Word, WordleSolver};
+use std::time::Duration;
+
+fn mean_guesses(scoring: Scoring) -> f64
+{
+    WordleSolver::new(small_dictionary(150), None, None, scoring)
+            .with_feedback_matrix(true)
+            .full_test(false)
+            .mean()
+}
+
+#[test]
+fn expected_scoring_keeps_up_with_eliminations()
+{
+    let eliminations = mean_guesses(Scoring::Eliminations);
+    let expected = mean_guesses(Scoring::Expected);
+    // The estimate is only a heuristic, so allow it to be slightly worse.
+    assert!(expected <= eliminations + 0.05,
+        "expected scoring needs {} guesses on average, eliminations {}", expected, eliminations);
+}
+
+#[test]
+fn expected_scoring_prefers_a_guess_that_might_win()
+{
+    let words: Vec<Word> = ["abcde", "fghij"].iter().map(|w| Word::new(w.to_string())).collect();
+    let answers: Vec<&Word> = words.iter().collect();
+    let constraint = Constraint::new(5);
+    // Both guesses split the answers into two groups of one, but only abcde
+    // can be the answer.
+    let candidate = Scoring::Expected.score(&words[0], &answers, &constraint);
+    let other = Scoring::Expected.score(&Word::new("abxyz".to_string()), &answers, &constraint);
+    assert_eq!(candidate, -0.5);
+    assert_eq!(other, -1.0);
+    for matrix in [false, true] {
+        let guesses = vec![Word::new("abxyz".to_string()), words[0].clone(), words[1].clone()];
+        let solver = WordleSolver::new(words.clone(), Some(guesses), None, Scoring::Expected)
+                .with_feedback_matrix(matrix)
+                .with_quiet(true);
+        assert_ne!(solver.best_guess(&constraint, false).unwrap().as_str(), "abxyz");
+    }
+}
+
+#[test]
+fn switching_scorers_forgets_the_first_guess()
+{
+    let words = small_dictionary(150);
+    let fresh = WordleSolver::new(words.clone(), None, None, Scoring::Minimax).with_feedback_matrix(true);
+    let minimax = fresh.best_guess(&Constraint::new(5), false).unwrap().as_str().to_string();
+
+    let solver = WordleSolver::new(words, None, None, Scoring::Eliminations).with_feedback_matrix(true);
+    solver.best_guess(&Constraint::new(5), false).unwrap();
+    let solver = solver.with_scoring(Scoring::Minimax);
+    assert_eq!(solver.first_guess(), None);
+    assert_eq!(solver.best_guess(&Constraint::new(5), false).unwrap().as_str(), minimax);
+    assert_eq!(solver.full_test(false).mean(), fresh.full_test(false).mean());
+}
+
+#[test]
+fn best_opener_scores_like_ranked_guesses()
+{
+    let words = small_dictionary(150);
+    for matrix in [false, true] {
+        let solver = WordleSolver::new(words.clone(), None, None, Scoring::Entropy).with_feedback_matrix(matrix);
+        let (opener, score) = solver.best_opener().unwrap();
+        let ranked = solver.ranked_guesses(&Constraint::new(5), 1).unwrap();
+        assert!(opener == ranked[0].0);
+        assert!((score - ranked[0].1).abs() < 1e-9, "{} against {}", score, ranked[0].1);
+        assert!(solver.guesses()[solver.first_guess().unwrap()] == *opener);
+    }
+
+    // Letter frequencies rank the openers differently from the real scores,
+    // and the score reported is the one the opener was picked by.
+    let solver = WordleSolver::new(words, None, None, Scoring::Entropy).with_exact_below(Some(1));
+    let (_opener, score) = solver.best_opener().unwrap();
+    let ranked = solver.ranked_guesses(&Constraint::new(5), 1).unwrap();
+    assert_eq!(score, ranked[0].1);
+}
+
+#[test]
+fn bench_scoring_scores_at_least_one_batch()
+{
+    let solver = small_solver(150);
+    let (scored, elapsed) = solver.bench_scoring(Duration::ZERO);
+    assert!(scored > 0);
+    assert!(elapsed > Duration::ZERO);
+}
+
+#[test]
+fn opener_strategies_pick_the_first_guess()
+{
+    let words = small_dictionary(150);
+    let solver = |opener: &str| WordleSolver::new(words.clone(), None, None, Scoring::Eliminations)
+            .with_feedback_matrix(true)
+            .with_opener_strategy(opener.parse().unwrap());
+    let first_guess = |opener: &str| solver(opener).best_guess(&Constraint::new(5), false).unwrap().as_str().to_string();
+
+    // adieu and audio both have four vowels and a d, so the first one wins.
+    assert_eq!(first_guess("vowels"), "adieu");
+    assert_eq!(first_guess("word:bagel"), "bagel");
+    assert!(solver("word:crane").best_guess(&Constraint::new(5), false).is_err());
+    assert_eq!(first_guess("score"), WordleSolver::new(words.clone(), None, None, Scoring::Eliminations)
+            .with_feedback_matrix(true)
+            .best_guess(&Constraint::new(5), false).unwrap().as_str());
+    // Only the first guess changes.
+    let later = Constraint::from_string("~a -d -i -e -u", 5).unwrap();
+    assert!(solver("vowels").best_guess(&later, false).unwrap() == solver("score").best_guess(&later, false).unwrap());
+
+    assert_eq!("word:crane".parse::<OpenerStrategy>().unwrap(), OpenerStrategy::Word("crane".to_string()));
+    assert!("word:".parse::<OpenerStrategy>().is_err());
+    assert!("best".parse::<OpenerStrategy>().is_err());
+}
+
+#[test]
+fn opener_stats_add_up_to_every_answer()
+{
+    let solver = small_solver(150).with_feedback_matrix(true);
+    let stats = solver.opener_stats().unwrap();
+    assert!(stats.opener == solver.best_guess(&Constraint::new(5), false).unwrap());
+    assert_eq!(stats.buckets.iter().map(|(left, patterns)| left * patterns).sum::<usize>(), 150);
+    assert_eq!(stats.max, *stats.buckets.keys().max().unwrap());
+    let squares: usize = solver.answers().iter()
+            .map(|answer| solver.remaining_count(&wordle_guess(stats.opener, answer)))
+            .sum();
+    assert!((stats.mean - squares as f64 / 150.0).abs() < 1e-9);
+}
+
+#[test]
+fn exact_below_plays_nearly_as_well()
+{
+    let words = small_dictionary(150);
+    let exact = mean_guesses(Scoring::Eliminations);
+    let solver = |exact_below| WordleSolver::new(words.clone(), None, None, Scoring::Eliminations)
+            .with_feedback_matrix(true)
+            .with_exact_below(exact_below);
+    // Never more words than the threshold, so the heuristic never kicks in.
+    assert_eq!(solver(Some(151)).full_test(false).mean(), exact);
+    let hybrid = solver(Some(50)).full_test(false).mean();
+    assert!((hybrid - exact).abs() < 0.2, "the hybrid needs {} guesses on average, the scorer {}", hybrid, exact);
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "server")]
+
+mod common;
+
+use common::small_solver;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+use wordle_solve::{serve, Constraint};
+
+const PORT: u16 = 18473;
+
+fn post(body: &str) -> String
+{
+    let mut stream = TcpStream::connect(("127.0.0.1", PORT)).unwrap();
+    write!(stream, "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(), body).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.split("\r\n\r\n").nth(1).unwrap().to_string()
+}
+
+#[test]
+fn serves_best_guesses()
+{
+    let solver = small_solver(150).with_quiet(true);
+    let expected = solver.best_guess(&Constraint::new(5), false).unwrap().as_str().to_string();
+    thread::spawn(move || serve(&solver, "127.0.0.1", PORT).unwrap());
+    thread::sleep(Duration::from_millis(200));
+
+    let response: serde_json::Value = serde_json::from_str(&post("[]")).unwrap();
+    assert_eq!(response["best_guess"], expected.as_str());
+    assert_eq!(response["remaining"], 150);
+
+    let response: serde_json::Value = serde_json::from_str(&post(r#"["b -l -o -n -e"]"#)).unwrap();
+    assert_eq!(response["best_guess"], "baggy");
+    assert_eq!(response["remaining"], 1);
+
+    for body in [r#"["b -l"]"#, "not json", r#"{"rows": []}"#, "[1, 2]"] {
+        let response: serde_json::Value = serde_json::from_str(&post(body)).unwrap();
+        assert!(response["error"].is_string(), "{}", body);
+    }
+    // The server is still up after the bad requests.
+    let response: serde_json::Value = serde_json::from_str(&post("[]")).unwrap();
+    assert_eq!(response["best_guess"], expected.as_str());
+}
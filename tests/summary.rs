@@ -0,0 +1,38 @@
+use wordle_solve::Summary;
+
+#[test]
+fn histogram_counts_every_guess_count()
+{
+    let mut summary = Summary::new();
+    for count in [3, 1, 3, 4, 3] {
+        summary.add(count);
+    }
+    summary.add_unsolved();
+    assert_eq!(summary.histogram(), vec![1, 0, 3, 1]);
+
+    let text = summary.to_string();
+    assert!(text.contains(&format!("\n 2 {} 0\n", " ".repeat(50))));
+    assert!(text.contains(&format!("\n 3 {} 3\n", "#".repeat(50))));
+    assert!(text.contains(&format!("\n 4 {}{} 1", "#".repeat(17), " ".repeat(33))));
+}
+
+#[test]
+fn digest_follows_every_game_in_order()
+{
+    let summary = |games: &[Option<usize>]| {
+        let mut summary = Summary::new();
+        for game in games {
+            match game {
+                Some(count) => summary.add(*count),
+                None => summary.add_unsolved()
+            }
+        }
+        summary.digest()
+    };
+    let digest = summary(&[Some(3), Some(4), None]);
+    assert_eq!(digest.len(), 64);
+    assert_eq!(digest, summary(&[Some(3), Some(4), None]));
+    assert_ne!(digest, summary(&[Some(4), Some(3), None]));
+    assert_ne!(digest, summary(&[Some(3), Some(4), Some(20)]));
+    assert_ne!(digest, summary(&[Some(3), Some(4)]));
+}
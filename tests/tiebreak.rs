@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use wordle_solve::{Constraint, Scoring, Tiebreak, Word, WordleSolver};
+
+fn words(words: &[&str]) -> Vec<Word>
+{
+    words.iter().map(|w| Word::new(w.to_string())).collect()
+}
+
+/// fgzzz and fgyxw split the answers the same way, but fgyxw tries five new
+/// letters instead of three.
+fn tied_solver() -> WordleSolver
+{
+    let answers = words(&["abcde", "abcdf", "abcdg"]);
+    let guesses = words(&["abcde", "abcdf", "abcdg", "fgyxw", "fgzzz"]);
+    WordleSolver::new(answers, Some(guesses), None, Scoring::Eliminations)
+}
+
+#[test]
+fn coverage_prefers_untested_letters()
+{
+    let constraint = Constraint::new(5);
+    let solver = tied_solver().with_tiebreak(Some(Tiebreak::Alpha));
+    let alpha = solver.best_guess(&constraint, false).unwrap().as_str().to_string();
+    assert_eq!(alpha, "fgzzz");
+    assert_eq!(tied_solver().best_guess(&constraint, false).unwrap().as_str(), alpha);
+    let coverage = tied_solver().with_tiebreak(Some(Tiebreak::Coverage));
+    assert_eq!(coverage.best_guess(&constraint, false).unwrap().as_str(), "fgyxw");
+}
+
+#[test]
+fn a_seed_alone_breaks_ties_at_random()
+{
+    let constraint = Constraint::new(5);
+    let guess = |seed| {
+        tied_solver().with_seed(Some(seed)).best_guess(&constraint, false).unwrap().as_str().to_string()
+    };
+    let guesses: HashSet<String> = (0..20).map(guess).collect();
+    assert!(guesses == HashSet::from(["fgyxw".to_string(), "fgzzz".to_string()]), "{:?}", guesses);
+    // The same seed always picks the same word.
+    assert_eq!(guess(3), guess(3));
+}
+
+#[test]
+fn untested_letters_skip_everything_known()
+{
+    let constraint = Constraint::from_string("-r ~a i -s -e", 5).unwrap();
+    assert_eq!(constraint.untested_letters(&Word::new("rains".to_string())), 1);
+    assert_eq!(constraint.untested_letters(&Word::new("tolly".to_string())), 4);
+}
+
+#[test]
+fn last_two_words_prefer_common_letters()
+{
+    // r and o are the most common letters here, and "error" has more of them.
+    for answers in [["fjord", "error"], ["error", "fjord"]] {
+        let solver = WordleSolver::new(words(&answers), None, None, Scoring::Eliminations);
+        assert_eq!(solver.best_guess(&Constraint::new(5), false).unwrap().as_str(), "error");
+    }
+}
+
+#[test]
+fn last_two_words_prefer_the_likelier_answer()
+{
+    // fjord has less common letters, but it's much more likely.
+    for answers in [["fjord", "error"], ["error", "fjord"]] {
+        let answers: Vec<Word> = words(&answers).into_iter()
+                .map(|word| if word.as_str() == "fjord" { word.with_weight(10.0) } else { word })
+                .collect();
+        let solver = WordleSolver::new(answers, None, None, Scoring::Eliminations);
+        assert_eq!(solver.best_guess(&Constraint::new(5), false).unwrap().as_str(), "fjord");
+    }
+}
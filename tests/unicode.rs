@@ -0,0 +1,60 @@
+use wordle_solve::{feedback_pattern, filter_words, read_words, wordle_guess, Constraint, Scoring,
+    Word, WordleSolver};
+
+fn words() -> Vec<Word>
+{
+    let path = format!("{}/tests/data/accented", env!("CARGO_MANIFEST_DIR"));
+    read_words(&path, None).unwrap().words
+}
+
+fn find<'a>(words: &'a [Word], word: &str) -> &'a Word
+{
+    words.iter().find(|w| w.as_str() == word).unwrap()
+}
+
+#[test]
+fn accented_letters_count_as_one_letter()
+{
+    let words = words();
+    assert_eq!(words.len(), 14);
+    assert!(words.iter().all(|w| w.len() == 5));
+    assert_eq!(find(&words, "muñón").char_count(&'ñ'), 1);
+    assert_eq!(find(&words, "muñón").char_count(&'o'), 0);
+}
+
+#[test]
+fn rows_accept_accented_letters()
+{
+    let words = words();
+    let constraint = Constraint::from_string("-s ~e ñ -a -l", 5).unwrap();
+    let remaining: Vec<&str> = filter_words(&constraint, &words).iter().map(|w| w.as_str()).collect();
+    assert_eq!(remaining, vec!["niñez"]);
+
+    let constraint = Constraint::from_string("~é -l -è -v -e", 5).unwrap();
+    let remaining: Vec<&str> = filter_words(&constraint, &words).iter().map(|w| w.as_str()).collect();
+    assert_eq!(remaining, vec!["héros", "début"]);
+}
+
+#[test]
+fn wordle_guess_handles_accented_letters()
+{
+    let words = words();
+    assert_eq!(feedback_pattern(find(&words, "señal"), find(&words, "niñez")), "XYGXX");
+    assert_eq!(feedback_pattern(find(&words, "muñón"), find(&words, "otoño")), "XXYXX");
+    for guess in &words {
+        for answer in &words {
+            let constraint = wordle_guess(guess, answer);
+            assert!(constraint.allows(answer), "{} vs {}", guess.as_str(), answer.as_str());
+        }
+    }
+}
+
+#[test]
+fn solver_finds_accented_answers()
+{
+    let words = words();
+    let solver = WordleSolver::new(words.clone(), None, None, Scoring::Eliminations);
+    for answer in &words {
+        assert!(solver.test(answer, false).is_solved(), "{} wasn't solved", answer.as_str());
+    }
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "wasm")]
+
+mod common;
+
+use common::{small_dictionary, small_solver};
+use wordle_solve::{best_guess_from_constraints, Constraint};
+
+#[test]
+fn best_guess_from_constraints_matches_the_solver()
+{
+    let words: String = small_dictionary(150).iter().map(|word| format!("{}\n", word.as_str())).collect();
+    let rows = vec!["-r ~a -i -s -e".to_string()];
+
+    let solver = small_solver(150);
+    let constraint = Constraint::from_string(&rows[0], 5).unwrap();
+    let expected = solver.best_guess(&constraint, false).unwrap().as_str();
+    assert_eq!(best_guess_from_constraints(&words, rows).unwrap(), expected);
+
+    assert!(best_guess_from_constraints("", Vec::new()).is_err());
+    assert!(best_guess_from_constraints(&words, vec!["-a -b".to_string()]).is_err());
+}